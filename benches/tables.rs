@@ -0,0 +1,63 @@
+//! Compares `Game::has_set` with the `CardSet` bitboard over many random deals.
+//! Run with `cargo bench -- <deal count>` (defaults to a million deals per table size).
+use std::{hint::black_box, time::Instant};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use rust_set::{bitset::CardSet, card::Card, game::Game};
+
+fn main() {
+    let deals = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<usize>().ok())
+        .unwrap_or(1_000_000);
+    let deck = (0u8..81)
+        .map(|num| Card::from_tile_number(num).unwrap())
+        .collect::<Vec<_>>();
+    println!("{deals} deals per table size");
+    for table_size in [12, 15, 18, 21] {
+        let mut rng = StdRng::seed_from_u64(0);
+        let tables = (0..deals)
+            .map(|_| {
+                deck.choose_multiple(&mut rng, table_size)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let started = Instant::now();
+        let naive = tables
+            .iter()
+            .filter(|table| Game::has_set(black_box(table.iter().copied())))
+            .count();
+        let naive_time = started.elapsed();
+
+        let started = Instant::now();
+        let bitboards = tables
+            .iter()
+            .map(|table| table.iter().copied().collect::<CardSet>())
+            .collect::<Vec<_>>();
+        let conversion_time = started.elapsed();
+
+        let started = Instant::now();
+        let fast = bitboards
+            .iter()
+            .filter(|table| black_box(**table).has_set())
+            .count();
+        let fast_time = started.elapsed();
+
+        let started = Instant::now();
+        let total_sets = bitboards
+            .iter()
+            .map(|table| black_box(*table).count_sets())
+            .sum::<usize>();
+        let enumeration_time = started.elapsed();
+
+        assert_eq!(naive, fast, "bitboard disagrees with Game::has_set");
+        println!(
+            "{table_size:>2} cards: {naive} tables with a set, {total_sets} sets in total
+    Game::has_set       {naive_time:>12.2?}
+    CardSet conversion  {conversion_time:>12.2?}
+    CardSet::has_set    {fast_time:>12.2?}
+    CardSet::sets       {enumeration_time:>12.2?}"
+        );
+    }
+}
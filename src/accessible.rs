@@ -0,0 +1,241 @@
+use std::{
+    io::{BufRead, Write},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    game::{Game, NoSetCall},
+    locale::{fill, text},
+};
+
+/// Plays a game without colours or cursor movement, for screen readers.
+/// Cards are described in words and numbered from 1; commands are read one per line.
+pub fn play(mut game: Game, input: impl BufRead, out: &mut impl Write) -> std::io::Result<()> {
+    writeln!(out, "{}", text("accessible.intro"))?;
+    writeln!(out, "{}", text("accessible.help"))?;
+    list_table(&game, out)?;
+    let started = Instant::now();
+    for line in input.lines() {
+        let line = line?;
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            [] => continue,
+            ["table" | "t"] => list_table(&game, out)?,
+            ["help" | "h"] => writeln!(out, "{}", text("accessible.help"))?,
+            ["quit" | "q"] => {
+                writeln!(out, "{}", text("accessible.stopped"))?;
+                return Ok(());
+            }
+            ["no", "set"] | ["n"] => {
+                if !game.config().call_no_set {
+                    writeln!(out, "{}", text("accessible.no_call_no_set"))?;
+                    continue;
+                }
+                match game.call_no_set() {
+                    NoSetCall::Correct => {
+                        writeln!(out, "{}", text("accessible.correct_no_set"))?;
+                        if game.ended.is_none() {
+                            list_table(&game, out)?;
+                        }
+                    }
+                    NoSetCall::Wrong(set) => writeln!(
+                        out,
+                        "{}",
+                        fill(
+                            "accessible.wrong_no_set",
+                            &[
+                                ("a", &(set[0] + 1)),
+                                ("b", &(set[1] + 1)),
+                                ("c", &(set[2] + 1))
+                            ]
+                        )
+                    )?,
+                }
+            }
+            ["select" | "s", numbers @ ..] => {
+                let Some(indices) = parse_selection(numbers, game.shown_count()) else {
+                    writeln!(
+                        out,
+                        "{}",
+                        fill(
+                            "accessible.bad_selection",
+                            &[("count", &game.shown_count())]
+                        )
+                    )?;
+                    continue;
+                };
+                for index in indices {
+                    game.select_card(index).unwrap();
+                }
+                match game.check_selected_set().unwrap() {
+                    Ok((card1, card2, card3)) => {
+                        writeln!(
+                            out,
+                            "{}",
+                            fill(
+                                "accessible.set_found",
+                                &[("a", &card3), ("b", &card2), ("c", &card1)]
+                            )
+                        )?;
+                        if game.ended.is_none() {
+                            list_table(&game, out)?;
+                        }
+                    }
+                    Err(mismatch) => {
+                        let (card1, card2, card3) = mismatch.cards;
+                        writeln!(
+                            out,
+                            "{} {}",
+                            fill(
+                                "accessible.not_set",
+                                &[("a", card1), ("b", card2), ("c", card3)]
+                            ),
+                            fill("accessible.broken", &[("attributes", &mismatch.describe())])
+                        )?
+                    }
+                }
+            }
+            _ => writeln!(out, "{}", text("accessible.unknown"))?,
+        }
+        if let Some(ended) = game.ended {
+            writeln!(
+                out,
+                "{}",
+                fill(
+                    "accessible.game_over",
+                    &[
+                        ("sets", &game.score.sets),
+                        ("time", &describe_duration(ended - started))
+                    ]
+                )
+            )?;
+            if game.config().call_no_set {
+                writeln!(
+                    out,
+                    "{}",
+                    fill("accessible.score", &[("score", &game.score.points())])
+                )?;
+            }
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+fn list_table(game: &Game, out: &mut impl Write) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "{}",
+        fill(
+            "accessible.table",
+            &[("shown", &game.shown_count()), ("pile", &game.pile_count())]
+        )
+    )?;
+    for (number, card) in (1..).zip(game.shown()) {
+        writeln!(out, "{number}: {card}")?;
+    }
+    Ok(())
+}
+
+/// Turns three distinct 1-based card numbers into indices
+fn parse_selection(numbers: &[&str], card_count: usize) -> Option<[usize; 3]> {
+    let indices = numbers
+        .iter()
+        .map(|number| number.parse::<usize>().ok()?.checked_sub(1))
+        .collect::<Option<Vec<_>>>()?;
+    let [a, b, c]: [usize; 3] = indices.try_into().ok()?;
+    let valid = a != b && b != c && a != c && [a, b, c].iter().all(|index| *index < card_count);
+    valid.then_some([a, b, c])
+}
+
+fn describe_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let plural = |n: u64, one: &'static str, many: &'static str| {
+        fill(if n == 1 { one } else { many }, &[("n", &n)])
+    };
+    let second = plural(seconds % 60, "duration.second", "duration.seconds");
+    if seconds >= 60 {
+        let minutes = plural(seconds / 60, "duration.minute", "duration.minutes");
+        fill(
+            "duration.and",
+            &[("minutes", &minutes), ("seconds", &second)],
+        )
+    } else {
+        second
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{card::Card, game::GameConfig};
+
+    /// Plays the commands on a deck where cards 3, 7 and 11 are the set of tiles 0, 1 and 2, returning the transcript
+    fn transcript(commands: &str) -> String {
+        let first = [3, 4, 0, 5, 6, 7, 1, 8, 9, 10, 2, 11];
+        let rest = (0..81).filter(|tile| !first.contains(tile));
+        let game = Game::new(first.into_iter().chain(rest), GameConfig::default());
+        let mut out = Vec::new();
+        play(game, commands.as_bytes(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn card(tile: u8) -> Card {
+        Card::from_tile_number(tile).unwrap()
+    }
+
+    #[test]
+    fn selecting_a_set() {
+        let transcript = transcript("select 3 7 11\n");
+        let found = fill(
+            "accessible.set_found",
+            &[("a", &card(0)), ("b", &card(1)), ("c", &card(2))],
+        );
+        assert!(transcript.contains(&found), "{transcript}");
+        // the table is listed again with the set replaced
+        let table = fill("accessible.table", &[("shown", &12), ("pile", &66)]);
+        assert!(transcript.contains(&table), "{transcript}");
+    }
+
+    #[test]
+    fn selecting_cards_which_are_not_a_set() {
+        let transcript = transcript("s 1 2 3\n");
+        let not_set = fill(
+            "accessible.not_set",
+            &[("a", &card(3)), ("b", &card(4)), ("c", &card(0))],
+        );
+        assert!(transcript.contains(&not_set), "{transcript}");
+    }
+
+    #[test]
+    fn invalid_numbers_are_explained() {
+        let commands = [
+            "select 1 2",
+            "select 0 2 3",
+            "select 1 1 2",
+            "select 1 2 13",
+            "select a b c",
+        ];
+        let transcript = transcript(&commands.join("\n"));
+        let bad_selection = fill("accessible.bad_selection", &[("count", &12)]);
+        assert_eq!(
+            transcript.matches(&bad_selection).count(),
+            commands.len(),
+            "{transcript}"
+        );
+        assert!(!transcript.contains(text("accessible.stopped")));
+    }
+
+    #[test]
+    fn quitting_stops_reading_commands() {
+        let transcript = transcript("frobnicate\nquit\nselect 3 7 11\n");
+        assert!(
+            transcript.contains(text("accessible.unknown")),
+            "{transcript}"
+        );
+        assert!(
+            transcript.ends_with(&format!("{}\n", text("accessible.stopped"))),
+            "{transcript}"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use crossterm::style::Color;
+
+use crate::{
+    card::Card,
+    game::{print_card, Event, Game},
+    layout::Layout,
+};
+
+/// How long found cards flash before the cards dealt in their place are shown
+const FOUND: Duration = Duration::from_millis(600);
+/// How long the cards of an invalid selection flash red
+const INVALID: Duration = Duration::from_millis(450);
+/// How long extra cards are highlighted once dealt
+const EXTRA: Duration = Duration::from_millis(900);
+/// Time between a flash turning on and off
+const FLASH: Duration = Duration::from_millis(150);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Found,
+    Invalid,
+    Extra,
+}
+
+impl Kind {
+    fn length(self) -> Duration {
+        match self {
+            Kind::Found => FOUND,
+            Kind::Invalid => INVALID,
+            Kind::Extra => EXTRA,
+        }
+    }
+}
+
+/// A card animated at its place on the table
+struct Animation {
+    kind: Kind,
+    started: Instant,
+    index: usize,
+    card: Card,
+}
+
+/// Short animations of the table's events, drawn over the table without holding up the game:
+/// found cards flash green before the cards replacing them are shown, invalid selections flash red,
+/// and extra cards dealt because there was no set are highlighted.
+pub struct Animations {
+    enabled: bool,
+    playing: Vec<Animation>,
+}
+
+impl Animations {
+    /// Animations which only play if `enabled`, so they can be turned off for players sensitive to motion and flashing
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            playing: Vec::new(),
+        }
+    }
+
+    /// Starts animating the events the game has queued since the last call, which are taken even if animations are off
+    pub fn start(&mut self, game: &mut Game) {
+        let events = game.take_events();
+        if !self.enabled {
+            return;
+        }
+        let mut started = Instant::now();
+        let shown = game.shown();
+        for event in events {
+            match event {
+                Event::Found { cards, indices } => {
+                    let found = indices.into_iter().zip(cards);
+                    self.playing.extend(found.map(|(index, card)| Animation {
+                        kind: Kind::Found,
+                        started,
+                        index,
+                        card,
+                    }));
+                    // cards dealt after a set is found are highlighted once the found cards stop flashing
+                    started += FOUND;
+                }
+                Event::Invalid { indices } => self.push(Kind::Invalid, started, &indices, shown),
+                Event::Dealt {
+                    indices,
+                    extra: true,
+                } => self.push(Kind::Extra, started, &indices, shown),
+                Event::Dealt { extra: false, .. } => {}
+            }
+        }
+    }
+
+    fn push(&mut self, kind: Kind, started: Instant, indices: &[usize], shown: &[Card]) {
+        let cards = indices
+            .iter()
+            .filter_map(|&index| Some((index, *shown.get(index)?)));
+        self.playing.extend(cards.map(|(index, card)| Animation {
+            kind,
+            started,
+            index,
+            card,
+        }));
+    }
+
+    /// Drops finished animations. Returns whether the table needs redrawing, which it does while anything is animated
+    /// and once more after the last animation finishes.
+    pub fn tick(&mut self) -> bool {
+        let now = Instant::now();
+        let animated = !self.playing.is_empty();
+        self.playing
+            .retain(|animation| now < animation.started + animation.kind.length());
+        animated
+    }
+
+    /// Draws the animated cards over the table, the card under the cursor reversed.
+    /// Cards which have since been taken off the table are not drawn, except found cards which are drawn in their old place.
+    pub fn draw(
+        &self,
+        out: &mut impl Write,
+        layout: &Layout,
+        game: &Game,
+        cursor: Option<usize>,
+    ) -> std::io::Result<()> {
+        let now = Instant::now();
+        for animation in &self.playing {
+            let Some(elapsed) = now.checked_duration_since(animation.started) else {
+                continue;
+            };
+            let on = (elapsed.as_millis() / FLASH.as_millis()).is_multiple_of(2);
+            let background = match animation.kind {
+                Kind::Found => on.then_some(Color::Green),
+                Kind::Invalid if on => Some(Color::Red),
+                Kind::Invalid => continue,
+                Kind::Extra => Some(Color::DarkCyan),
+            };
+            let moved = game.shown().get(animation.index) != Some(&animation.card);
+            if animation.kind != Kind::Found && moved {
+                continue;
+            }
+            let index = animation.index;
+            print_card(
+                out,
+                layout,
+                index,
+                &animation.card,
+                background,
+                cursor == Some(index),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::GameConfig, layout::CardSize};
+
+    /// A game whose first 12 cards have no set, so 3 extra cards are dealt at indices 12 to 14,
+    /// the last of which makes a set with the cards at 0 and 2
+    fn game(config: GameConfig) -> Game {
+        let first = [0, 1, 3, 4, 9, 10, 12, 13, 27, 28, 32, 35];
+        let rest = (0..81).filter(|tile| !first.contains(tile));
+        Game::new(first.into_iter().chain(rest), config)
+    }
+
+    fn select(game: &mut Game, indices: [usize; 3]) {
+        for index in indices {
+            game.select_card(index).unwrap();
+        }
+        game.check_selected_set();
+    }
+
+    fn playing(animations: &Animations) -> Vec<(Kind, usize)> {
+        let playing = animations.playing.iter();
+        playing
+            .map(|animation| (animation.kind, animation.index))
+            .collect()
+    }
+
+    #[test]
+    fn events_are_taken_even_when_animations_are_off() {
+        let mut game = game(GameConfig::default());
+        let mut animations = Animations::new(false);
+        select(&mut game, [0, 1, 2]);
+        animations.start(&mut game);
+        assert!(game.take_events().is_empty());
+        assert!(animations.playing.is_empty());
+        assert!(!animations.tick());
+    }
+
+    #[test]
+    fn each_event_animates_its_cards() {
+        let config = GameConfig {
+            call_no_set: true,
+            ..GameConfig::default()
+        };
+        let mut game = game(config);
+        let mut animations = Animations::new(true);
+        select(&mut game, [0, 1, 2]);
+        game.call_no_set();
+        animations.start(&mut game);
+        assert!(
+            playing(&animations)
+                == [
+                    (Kind::Invalid, 0),
+                    (Kind::Invalid, 1),
+                    (Kind::Invalid, 2),
+                    (Kind::Extra, 12),
+                    (Kind::Extra, 13),
+                    (Kind::Extra, 14)
+                ]
+        );
+        select(&mut game, [0, 2, 14]);
+        animations.start(&mut game);
+        let found = [(Kind::Found, 0), (Kind::Found, 2), (Kind::Found, 14)];
+        assert!(playing(&animations)[6..] == found);
+    }
+
+    #[test]
+    fn finished_animations_are_dropped_after_one_more_redraw() {
+        let mut game = game(GameConfig::default());
+        let mut animations = Animations::new(true);
+        select(&mut game, [0, 1, 2]);
+        animations.start(&mut game);
+        assert!(animations.tick());
+        assert_eq!(animations.playing.len(), 3);
+        for animation in &mut animations.playing {
+            animation.started -= INVALID;
+        }
+        assert!(animations.tick());
+        assert!(animations.playing.is_empty());
+        assert!(!animations.tick());
+    }
+
+    #[test]
+    fn only_found_cards_are_drawn_once_taken_off_the_table() {
+        let layout = Layout::new(40, 10, 15, (30, 4), CardSize::Compact).unwrap();
+        let mut game = game(GameConfig::default());
+        let mut animations = Animations::new(true);
+        let card = game.shown()[14];
+        for kind in [Kind::Found, Kind::Extra] {
+            animations.playing = vec![Animation {
+                kind,
+                started: Instant::now(),
+                index: 14,
+                card,
+            }];
+            let mut out = Vec::new();
+            animations.draw(&mut out, &layout, &game, None).unwrap();
+            assert!(!out.is_empty());
+        }
+        select(&mut game, [0, 2, 14]);
+        assert!(game.shown().get(14) != Some(&card));
+        for (kind, drawn) in [(Kind::Found, true), (Kind::Extra, false)] {
+            animations.playing[0].kind = kind;
+            let mut out = Vec::new();
+            animations.draw(&mut out, &layout, &game, None).unwrap();
+            assert_eq!(!out.is_empty(), drawn);
+        }
+    }
+}
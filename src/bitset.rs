@@ -0,0 +1,195 @@
+use crate::card::Card;
+
+/// Number of distinct cards in a deck
+pub const DECK_SIZE: u8 = 81;
+
+/// Lookup table such that `THIRD[a][b]` is the tile number completing a set with tiles `a` and `b`
+static THIRD: [[u8; DECK_SIZE as usize]; DECK_SIZE as usize] = third_table();
+
+const fn third_table() -> [[u8; DECK_SIZE as usize]; DECK_SIZE as usize] {
+    let mut table = [[0u8; DECK_SIZE as usize]; DECK_SIZE as usize];
+    let mut a = 0;
+    while a < DECK_SIZE {
+        let mut b = 0;
+        while b < DECK_SIZE {
+            // each trit of the third card is -(x + y) mod 3
+            let (mut x, mut y, mut trit_value, mut third) = (a, b, 1, 0);
+            while x > 0 || y > 0 {
+                third += (6 - x % 3 - y % 3) % 3 * trit_value;
+                x /= 3;
+                y /= 3;
+                trit_value *= 3;
+            }
+            table[a as usize][b as usize] = third;
+            b += 1;
+        }
+        a += 1;
+    }
+    table
+}
+
+/// Tile-number version of `Card::third`
+pub fn third(a: u8, b: u8) -> u8 {
+    THIRD[a as usize][b as usize]
+}
+
+/// A bitboard of cards, where bit n is set if the card with tile number n is in the set.
+/// Used for quickly analysing tables and decks, e.g. in simulations.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct CardSet(pub u128);
+
+impl CardSet {
+    pub const EMPTY: Self = Self(0);
+    pub const FULL: Self = Self((1 << DECK_SIZE) - 1);
+
+    pub fn contains(self, tile: u8) -> bool {
+        tile < DECK_SIZE && self.0 & (1 << tile) != 0
+    }
+
+    pub fn insert(&mut self, tile: u8) {
+        self.0 |= 1 << tile;
+    }
+
+    pub fn remove(&mut self, tile: u8) {
+        self.0 &= !(1 << tile);
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the tile numbers in this set in ascending order
+    pub fn iter(self) -> impl Iterator<Item = u8> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let tile = bits.trailing_zeros() as u8;
+            bits &= bits - 1;
+            Some(tile)
+        })
+    }
+
+    /// Iterates over every set within this collection of cards exactly once, as tile numbers in ascending order.
+    /// Runs in O(n²) time, as the third card of each pair is looked up instead of searched for.
+    pub fn sets(self) -> impl Iterator<Item = [u8; 3]> {
+        self.iter().flat_map(move |a| {
+            let after_a = CardSet(self.0 & !((2 << a) - 1));
+            after_a.iter().filter_map(move |b| {
+                let c = third(a, b);
+                (c > b && self.contains(c)).then_some([a, b, c])
+            })
+        })
+    }
+
+    pub fn has_set(self) -> bool {
+        self.sets().next().is_some()
+    }
+
+    pub fn count_sets(self) -> usize {
+        self.sets().count()
+    }
+}
+
+impl<'a> FromIterator<&'a Card> for CardSet {
+    fn from_iter<T: IntoIterator<Item = &'a Card>>(iter: T) -> Self {
+        iter.into_iter().map(Card::to_tile_number).collect()
+    }
+}
+
+impl FromIterator<u8> for CardSet {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut set = Self::EMPTY;
+        iter.into_iter().for_each(|tile| set.insert(tile));
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, seq::index::sample, SeedableRng};
+
+    /// A table of 20 cards with no set, the most there can be
+    const CAP_SET: [u8; 20] = [
+        0, 1, 3, 4, 9, 10, 12, 13, 27, 28, 32, 35, 38, 47, 59, 65, 66, 67, 71, 77,
+    ];
+
+    /// Every set among the tiles, found by trying every triple
+    fn brute_force(tiles: &[u8]) -> Vec<[u8; 3]> {
+        let mut tiles = tiles.to_vec();
+        tiles.sort();
+        let mut sets = Vec::new();
+        for (i, &a) in tiles.iter().enumerate() {
+            for (j, &b) in tiles.iter().enumerate().skip(i + 1) {
+                for &c in &tiles[j + 1..] {
+                    let card = |tile| Card::from_tile_number(tile).unwrap();
+                    if card(a).third(&card(b)) == card(c) {
+                        sets.push([a, b, c]);
+                    }
+                }
+            }
+        }
+        sets
+    }
+
+    fn board(rng: &mut StdRng, size: usize) -> Vec<u8> {
+        sample(rng, DECK_SIZE.into(), size)
+            .into_iter()
+            .map(|tile| tile as u8)
+            .collect()
+    }
+
+    #[test]
+    fn third_matches_cards() {
+        for a in 0..DECK_SIZE {
+            for b in 0..DECK_SIZE {
+                let card = |tile| Card::from_tile_number(tile).unwrap();
+                assert_eq!(third(a, b), card(a).third(&card(b)).to_tile_number());
+            }
+        }
+    }
+
+    #[test]
+    fn sets_match_brute_force() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for size in (3..=21).chain([81]).flat_map(|size| [size; 20]) {
+            let tiles = board(&mut rng, size);
+            let cards = tiles.iter().copied().collect::<CardSet>();
+            let expected = brute_force(&tiles);
+            assert_eq!(cards.sets().collect::<Vec<_>>(), expected, "{tiles:?}");
+            assert_eq!(cards.has_set(), !expected.is_empty());
+            assert_eq!(cards.count_sets(), expected.len());
+        }
+    }
+
+    #[test]
+    fn cap_set_has_no_set() {
+        let cap = CAP_SET.into_iter().collect::<CardSet>();
+        assert!(brute_force(&CAP_SET).is_empty());
+        assert!(!cap.has_set());
+        assert_eq!(cap.count_sets(), 0);
+        // adding any other card makes a set, as no 21 cards are free of sets
+        for tile in (0..DECK_SIZE).filter(|tile| !cap.contains(*tile)) {
+            let mut board = cap;
+            board.insert(tile);
+            assert!(board.has_set(), "{tile}");
+        }
+    }
+
+    #[test]
+    fn every_21_card_board_has_a_set() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..1000 {
+            assert!(board(&mut rng, 21)
+                .into_iter()
+                .collect::<CardSet>()
+                .has_set());
+        }
+    }
+}
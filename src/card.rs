@@ -1,6 +1,8 @@
-use crossterm::style::{Attribute, PrintStyledContent, StyledContent, Stylize};
+use crossterm::style::{Color, PrintStyledContent, StyledContent, Stylize};
 
-#[derive(PartialEq, Eq)]
+use crate::{names::CardNames, theme::Theme};
+
+#[derive(PartialEq, Eq, Clone, Copy)]
 pub struct Card {
     pub letter: Letter,
     pub count: Count,
@@ -23,6 +25,11 @@ impl Card {
         })
     }
 
+    /// Inverse of `Self::from_tile_number`
+    pub fn to_tile_number(&self) -> u8 {
+        self.letter as u8 + 3 * self.count as u8 + 9 * self.styling as u8 + 27 * self.colour as u8
+    }
+
     /// It is always possible to form a set with two cards and one other unique card.
     /// This function returns such card.
     pub fn third(&self, other: &Self) -> Card {
@@ -34,26 +41,197 @@ impl Card {
         }
     }
 
+    /// The attributes of three cards which are neither all the same nor all different.
+    /// The cards form a set if there are none.
+    pub fn broken_attributes(cards: [&Card; 3]) -> Vec<Attribute> {
+        ATTRIBUTES
+            .into_iter()
+            .filter(|attribute| {
+                let [a, b, c] = cards.map(|card| attribute.value(card));
+                let same = a == b && b == c;
+                let different = a != b && b != c && a != c;
+                !(same || different)
+            })
+            .collect()
+    }
+
+    /// This card with one attribute changed to the value numbered `value`, in the order of the attribute's enum
+    pub fn with(&self, attribute: Attribute, value: u8) -> Card {
+        let weight = attribute.weight();
+        let tile = self.to_tile_number();
+        Card::from_tile_number(tile - attribute.value(self) as u8 * weight + value % 3 * weight)
+            .unwrap()
+    }
+
     // Formatting
 
-    /// Get a StyledContent representing this tile
+    /// Get a StyledContent representing this tile in the active theme
     fn get_inner_style(&self) -> StyledContent<String> {
+        let theme = Theme::active();
+        let mut text = self
+            .count
+            .apply(theme.letters[self.letter as usize].to_string());
+        if let Some(marks) = theme.decoration_marks {
+            text.push(marks[self.styling as usize]);
+        }
+        if let Some(marks) = theme.colour_marks {
+            text.push(marks[self.colour as usize]);
+        }
         self.colour
-            .apply(self.styling.apply(self.count.apply(self.letter.print())))
+            .apply(self.styling.apply(text.stylize(), theme), theme)
     }
 
     /// Returns an instruction to print an (un)highlighted version of this tile
     pub fn stylise(&self, highlighted: bool) -> PrintStyledContent<String> {
         if highlighted {
-            PrintStyledContent(self.get_inner_style().on(crossterm::style::Color::DarkGrey))
+            self.stylise_on(Color::DarkGrey)
         } else {
             PrintStyledContent(self.get_inner_style())
         }
     }
+
+    /// Returns an instruction to print this tile on a given background colour
+    pub fn stylise_on(&self, background: Color) -> PrintStyledContent<String> {
+        PrintStyledContent(self.get_inner_style().on(background))
+    }
+
+    /// Returns instructions to print each line of a large, boxed version of this tile, from top to bottom.
+    /// Shapes are stacked vertically, and the fill is drawn with glyphs instead of underlines.
+    pub fn stylise_big(
+        &self,
+        background: Option<Color>,
+    ) -> [PrintStyledContent<String>; BIG_HEIGHT as usize] {
+        let theme = Theme::active();
+        let (left, right) = self.letter.outline();
+        let (top, side, bottom) = if theme.ascii {
+            ("+-------+", '|', "+-------+")
+        } else {
+            ("┌───────┐", '│', "└───────┘")
+        };
+        let shape = format!(
+            "{side} {left}{fill}{right} {side}",
+            fill = self.styling.fill(theme.ascii)
+        );
+        let blank = format!("{side}       {side}");
+        // the colour mark sits in the middle of the top border
+        let top = match theme.colour_marks {
+            Some(marks) => top
+                .chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    if i == 4 {
+                        marks[self.colour as usize]
+                    } else {
+                        c
+                    }
+                })
+                .collect(),
+            None => top.to_owned(),
+        };
+        let rows = self.count.rows();
+        let lines = [
+            top,
+            if rows[0] {
+                shape.clone()
+            } else {
+                blank.clone()
+            },
+            if rows[1] {
+                shape.clone()
+            } else {
+                blank.clone()
+            },
+            if rows[2] { shape } else { blank },
+            bottom.to_owned(),
+        ];
+        lines.map(|line| {
+            let styled = self.colour.apply(line.stylize(), theme);
+            PrintStyledContent(match background {
+                Some(background) => styled.on(background),
+                None => styled,
+            })
+        })
+    }
+}
+
+/// Describes a card in words, e.g. "three purple double-underlined squiggles"
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = CardNames::active();
+        let number = usize::from(self.count != Count::One);
+        let description = names
+            .card
+            .replace("{count}", &self.count.to_string())
+            .replace(
+                "{colour}",
+                names.colour_names(Theme::active())[self.colour as usize][number],
+            )
+            .replace(
+                "{decoration}",
+                names.decorations[self.styling as usize][number],
+            )
+            .replace("{shape}", names.shapes[self.letter as usize][number]);
+        f.write_str(&description)
+    }
+}
+
+/// One of the four properties of a card, each of which must be all the same or all different in a set
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Attribute {
+    Shape,
+    Number,
+    Decoration,
+    Colour,
+}
+
+/// Every attribute, in the order they are introduced in the tutorial
+pub static ATTRIBUTES: [Attribute; 4] = [
+    Attribute::Shape,
+    Attribute::Number,
+    Attribute::Colour,
+    Attribute::Decoration,
+];
+
+impl Attribute {
+    /// The number of this attribute's value on a card, in the order of its enum
+    pub fn value(&self, card: &Card) -> usize {
+        match self {
+            Self::Shape => card.letter as usize,
+            Self::Number => card.count as usize,
+            Self::Decoration => card.styling as usize,
+            Self::Colour => card.colour as usize,
+        }
+    }
+
+    /// Names this attribute's value on a card, e.g. "red"
+    pub fn describe(&self, card: &Card) -> String {
+        match self {
+            Self::Shape => card.letter.to_string(),
+            Self::Number => card.count.to_string(),
+            Self::Decoration => card.styling.to_string(),
+            Self::Colour => card.colour.to_string(),
+        }
+    }
+
+    /// What one step of this attribute's value adds to a tile number
+    fn weight(&self) -> u8 {
+        3u8.pow(*self as u32)
+    }
+}
+
+impl std::fmt::Display for Attribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(CardNames::active().attributes[*self as usize])
+    }
 }
 
+/// Width of a card printed by `Card::stylise_big`
+pub const BIG_WIDTH: u16 = 9;
+/// Height of a card printed by `Card::stylise_big`
+pub const BIG_HEIGHT: u16 = 5;
+
 /// A representation of the letter on a card: Cylinders (rendered as O), Diamonds (rendered as >, D for diamond) and squigglies (rendered as S)
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Letter {
     O,
     D,
@@ -69,11 +247,12 @@ impl Letter {
         }
     }
 
-    fn print(&self) -> String {
+    /// The left and right edges of this shape on a big card
+    fn outline(&self) -> (char, char) {
         match self {
-            Self::O => "O".to_owned(),
-            Self::D => ">".to_owned(),
-            Self::S => "S".to_owned(),
+            Self::O => ('(', ')'),
+            Self::D => ('<', '>'),
+            Self::S => ('{', '}'),
         }
     }
 
@@ -98,8 +277,14 @@ impl Letter {
     }
 }
 
+impl std::fmt::Display for Letter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(CardNames::active().shapes[*self as usize][0])
+    }
+}
+
 /// A representation of how many shapes are on the card
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Count {
     One,
     Two,
@@ -115,6 +300,15 @@ impl Count {
         }
     }
 
+    /// Which of the three shape rows on a big card are drawn
+    fn rows(&self) -> [bool; 3] {
+        match self {
+            Self::One => [false, true, false],
+            Self::Two => [true, false, true],
+            Self::Three => [true, true, true],
+        }
+    }
+
     fn from_number(num: u8) -> Option<Self> {
         match num {
             0 => Some(Self::One),
@@ -145,20 +339,40 @@ impl Count {
     }
 }
 
+impl std::fmt::Display for Count {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(CardNames::active().counts[*self as usize])
+    }
+}
+
 /// A representation of the fill of a card: Empty (rendered as no decoration), Striped (represented as one underline), Filled (originally represented as bold, but changed to double underline)
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Styling {
     None,
     Underline,
     DoubleUnderline,
 }
 impl Styling {
-    fn apply<T: Stylize>(&self, string: T) -> T::Styled {
-        match self {
-            Self::None => string.attribute(Attribute::NoUnderline),
-            Self::Underline => string.attribute(Attribute::Underlined),
-            // some terminals interpret no bold as double underline???
-            Self::DoubleUnderline => string.attribute(Attribute::NoBold),
+    /// Underlines the content, unless the theme spells out decorations with marks
+    pub(crate) fn apply(
+        &self,
+        string: StyledContent<String>,
+        theme: &Theme,
+    ) -> StyledContent<String> {
+        if theme.decoration_marks.is_some() {
+            return string;
+        }
+        string.attribute(theme.decorations[*self as usize])
+    }
+
+    /// The inside of a shape on a big card: empty, striped or solid
+    fn fill(&self, ascii: bool) -> &'static str {
+        match (self, ascii) {
+            (Self::None, _) => "   ",
+            (Self::Underline, false) => "░░░",
+            (Self::Underline, true) => "///",
+            (Self::DoubleUnderline, false) => "███",
+            (Self::DoubleUnderline, true) => "###",
         }
     }
 
@@ -192,20 +406,29 @@ impl Styling {
     }
 }
 
+impl std::fmt::Display for Styling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(CardNames::active().decorations[*self as usize][0])
+    }
+}
 
 /// Representation of the colour of a card. Colour, not color.
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Colour {
     Red,
     Green,
     Purple,
 }
 impl Colour {
-    fn apply<T: Stylize>(&self, string: T) -> T::Styled {
-        match self {
-            Self::Red => string.red(),
-            Self::Green => string.green(),
-            Self::Purple => string.magenta(),
+    /// Colours the content with the theme's colour for this card colour
+    pub(crate) fn apply(
+        &self,
+        string: StyledContent<String>,
+        theme: &Theme,
+    ) -> StyledContent<String> {
+        match theme.colours {
+            Some(colours) => string.with(colours[*self as usize]),
+            None => string,
         }
     }
 
@@ -238,3 +461,9 @@ impl Colour {
         }
     }
 }
+
+impl std::fmt::Display for Colour {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(CardNames::active().colour_names(Theme::active())[*self as usize][0])
+    }
+}
@@ -0,0 +1,186 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    game::{GameConfig, DEFAULT_TIME_LIMIT},
+    keymap::{self, Keymap},
+    layout::CardSize,
+    locale::{fill, text, Locale, ENGLISH, LOCALES},
+    puzzle,
+    theme::{self, Theme},
+};
+
+/// The language chosen with `--lang`, or else the one set by the environment, or English.
+/// Found before the arguments are parsed, so that any problems with them can be reported in it.
+pub fn language(args: &[String]) -> &'static Locale {
+    args.iter()
+        .position(|arg| arg == "--lang")
+        .and_then(|index| Locale::find(args.get(index + 1)?))
+        .or_else(Locale::from_env)
+        .unwrap_or(&ENGLISH)
+}
+
+/// Options collected from the command line
+#[derive(Default)]
+pub struct Options {
+    pub game: GameConfig,
+    pub card_size: CardSize,
+    /// Theme chosen on the command line, taking priority over the config file
+    pub theme: Option<Theme>,
+    /// Keyboard layout chosen on the command line, taking priority over the config file
+    pub keymap: Option<Keymap>,
+    /// Config file to use instead of the default one
+    pub config: Option<PathBuf>,
+    /// Print an example config file instead of playing
+    pub print_config: bool,
+    /// Number of sets on a puzzle board
+    pub puzzle_sets: usize,
+    /// Length of a time-attack game
+    pub time_limit: Duration,
+    /// Play in the line-oriented mode for screen readers
+    pub accessible: bool,
+    /// Animate found and invalid sets and extra cards
+    pub animations: bool,
+}
+
+impl Options {
+    /// Parses command line arguments (excluding the program name).
+    /// Returns Ok(None) if help was requested; otherwise returns the options or a message describing the invalid argument.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut options = Options {
+            puzzle_sets: puzzle::DEFAULT_SETS,
+            time_limit: DEFAULT_TIME_LIMIT,
+            animations: true,
+            ..Options::default()
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| fill("args.requires_value", &[("arg", &arg)]))
+            };
+            match arg.as_str() {
+                "--help" | "-h" => return Ok(None),
+                "--preset" => {
+                    let preset = value()?;
+                    options.game = match preset.as_str() {
+                        "standard" => GameConfig::default(),
+                        "speed" => GameConfig::speed(),
+                        "relaxed" => GameConfig::relaxed(),
+                        _ => return Err(fill("args.unknown_preset", &[("preset", &preset)])),
+                    }
+                }
+                "--table-size" => options.game.table_size = parse_number(&arg, value()?)?,
+                "--extra-draw" => options.game.extra_draw = parse_number(&arg, value()?)?,
+                "--refill-extras" => options.game.refill_extras = true,
+                "--call-no-set" => options.game.call_no_set = true,
+                "--theme" => {
+                    let name = value()?;
+                    options.theme = Some(Theme::preset(&name).ok_or_else(|| {
+                        fill(
+                            "error.unknown_theme",
+                            &[("name", &name), ("expected", &theme::PRESETS.join(", "))],
+                        )
+                    })?);
+                }
+                "--keys" => {
+                    let name = value()?;
+                    options.keymap = Some(Keymap::preset(&name).ok_or_else(|| {
+                        fill(
+                            "error.unknown_keys",
+                            &[("name", &name), ("expected", &keymap::PRESETS.join(", "))],
+                        )
+                    })?);
+                }
+                // the language itself is set by `language` before parsing
+                "--lang" => {
+                    let code = value()?;
+                    if Locale::find(&code).is_none() {
+                        let codes = LOCALES.map(|locale| locale.code);
+                        return Err(fill(
+                            "args.unknown_lang",
+                            &[("code", &code), ("expected", &codes.join(", "))],
+                        ));
+                    }
+                }
+                "--config" => options.config = Some(PathBuf::from(value()?)),
+                "--print-config" => options.print_config = true,
+                "--accessible" => options.accessible = true,
+                "--no-animations" => options.animations = false,
+                "--puzzle-sets" => {
+                    options.puzzle_sets = parse_number(&arg, value()?)?;
+                    if !(1..=puzzle::MAX_SETS).contains(&options.puzzle_sets) {
+                        return Err(fill("args.puzzle_sets", &[("max", &puzzle::MAX_SETS)]));
+                    }
+                }
+                "--time-limit" => {
+                    let seconds = parse_number(&arg, value()?)?;
+                    if seconds == 0 {
+                        return Err(text("args.time_limit").to_owned());
+                    }
+                    options.time_limit = Duration::from_secs(seconds as u64);
+                }
+                "--cards" => {
+                    let card_size = value()?;
+                    options.card_size = match card_size.as_str() {
+                        "auto" => CardSize::Auto,
+                        "big" => CardSize::Big,
+                        "compact" => CardSize::Compact,
+                        _ => return Err(fill("args.unknown_cards", &[("cards", &card_size)])),
+                    }
+                }
+                _ => return Err(fill("args.unknown", &[("arg", &arg)])),
+            }
+        }
+        options.game.validate()?;
+        Ok(Some(options))
+    }
+}
+
+fn parse_number(arg: &str, value: String) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| fill("args.not_a_number", &[("arg", &arg), ("value", &value)]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_game_options() {
+        let options = parse(&["--preset", "speed", "--extra-draw", "6", "--call-no-set"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.game.table_size, 9);
+        assert_eq!(options.game.extra_draw, 6);
+        assert!(options.game.call_no_set);
+        assert!(parse(&["--help"]).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let invalid: &[&[&str]] = &[
+            &["--unknown"],
+            &["--table-size"],
+            &["--table-size", "twelve"],
+            &["--table-size", "13"],
+            &["--extra-draw", "0"],
+            &["--preset", "huge"],
+            &["--cards", "tiny"],
+            &["--theme", "neon"],
+            &["--keys", "bepo"],
+            &["--lang", "xx"],
+            &["--puzzle-sets", "0"],
+            &["--puzzle-sets", "15"],
+            &["--time-limit", "0"],
+        ];
+        for args in invalid {
+            let error = parse(args).err();
+            assert!(error.is_some(), "{args:?} was accepted");
+        }
+    }
+}
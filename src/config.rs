@@ -0,0 +1,348 @@
+use std::path::{Path, PathBuf};
+
+use crossterm::style::{Attribute, Color};
+use serde::Deserialize;
+
+use crate::{
+    keymap::{self, Keymap},
+    locale::fill,
+    theme::{Theme, PRESETS},
+};
+
+/// An example config file, listing every option with its default value.
+/// The `[cards]` options override the theme, so they are commented out.
+pub static EXAMPLE: &str = r##"# Built-in theme to start from: standard, colour-blind, glyph or ascii
+theme = "standard"
+
+# Each option below replaces the theme's own; uncomment it to change it
+[cards]
+# Glyphs for pills, diamonds and squiggles, each one character one column wide
+# shapes = ["O", ">", "S"]
+# Colours of red, green and purple cards, as names (e.g. "dark_red") or hex codes (e.g. "#cc79a7").
+# An empty list prints cards without colour.
+# colours = ["red", "green", "magenta"]
+# Letters spelling out the colour of each card; an empty list adds none
+# colour_marks = []
+# Attributes for cards with no underline, one underline and two underlines
+# decorations = ["no_underline", "underlined", "no_bold"]
+# Characters spelling out the decoration of each card, replacing the attributes above; an empty list adds none
+# decoration_marks = []
+# Draw big cards with ASCII instead of box-drawing characters
+# ascii = false
+
+[keys]
+# Built-in keyboard layout: qwerty, qwertz, azerty, dvorak or colemak
+layout = "qwerty"
+# Keys selecting the cards on each row of the table, replacing the layout above
+# grid = ["qwertyu", "asdfghj", "zxcvbnm"]
+"##;
+
+/// Overrides read from the config file. Every field is optional.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    theme: Option<String>,
+    #[serde(default)]
+    cards: CardsSection,
+    #[serde(default)]
+    keys: KeysSection,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct CardsSection {
+    shapes: Option<Vec<String>>,
+    colours: Option<Vec<String>>,
+    colour_marks: Option<Vec<String>>,
+    decorations: Option<Vec<String>>,
+    decoration_marks: Option<Vec<String>>,
+    ascii: Option<bool>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct KeysSection {
+    layout: Option<String>,
+    grid: Option<[String; 3]>,
+}
+
+/// Everything that can be customised through the config file
+#[derive(Default)]
+pub struct Config {
+    pub theme: Theme,
+    pub keymap: Keymap,
+}
+
+impl Config {
+    /// Where the config file is read from if no path is given:
+    /// `rust-set/config.toml` in `$XDG_CONFIG_HOME`, `%APPDATA%` or `~/.config`
+    pub fn default_path() -> Option<PathBuf> {
+        let dir = std::env::var_os("XDG_CONFIG_HOME")
+            .or_else(|| std::env::var_os("APPDATA"))
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(dir.join("rust-set").join("config.toml"))
+    }
+
+    /// Reads the config file at path. `theme` and `keymap`, if given, take priority over the file:
+    /// its theme and `[cards]` options are skipped for a given theme, and its `[keys]` options for a given keymap.
+    /// A missing file is not an error. Invalid options are skipped, and returned as a list of problems.
+    pub fn load(path: &Path, theme: Option<Theme>, keymap: Option<Keymap>) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let file = match std::fs::read_to_string(path) {
+            Ok(text) => match toml::from_str::<ConfigFile>(&text) {
+                Ok(file) => file,
+                Err(error) => {
+                    let line = error
+                        .span()
+                        .map_or(0, |span| text[..span.start].matches('\n').count() + 1);
+                    errors.push(fill(
+                        "config.line",
+                        &[("line", &line), ("error", &error.message())],
+                    ));
+                    ConfigFile::default()
+                }
+            },
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => ConfigFile::default(),
+            Err(error) => {
+                errors.push(error.to_string());
+                ConfigFile::default()
+            }
+        };
+        let theme_given = theme.is_some();
+        let keymap_given = keymap.is_some();
+        let mut config = Config {
+            theme: theme.unwrap_or_default(),
+            keymap: keymap.unwrap_or_default(),
+        };
+        if let (false, Some(name)) = (theme_given, &file.theme) {
+            match Theme::preset(name) {
+                Some(theme) => config.theme = theme,
+                None => errors.push(fill(
+                    "error.unknown_theme",
+                    &[("name", name), ("expected", &PRESETS.join(", "))],
+                )),
+            }
+        }
+        let mut check = |result: Result<(), String>| {
+            if let Err(error) = result {
+                errors.push(error);
+            }
+        };
+        // a theme given on the command line replaces the file's as a whole
+        let cards = if theme_given {
+            CardsSection::default()
+        } else {
+            file.cards
+        };
+        let theme = &mut config.theme;
+        if let Some(shapes) = cards.shapes {
+            check(triple("cards.shapes", &shapes, glyph).map(|letters| theme.letters = letters));
+        }
+        if let Some(colours) = cards.colours {
+            check(
+                optional_triple("cards.colours", &colours, colour)
+                    .map(|colours| theme.colours = colours),
+            );
+        }
+        if let Some(marks) = cards.colour_marks {
+            check(
+                optional_triple("cards.colour_marks", &marks, glyph)
+                    .map(|marks| theme.colour_marks = marks),
+            );
+        }
+        if let Some(decorations) = cards.decorations {
+            check(
+                triple("cards.decorations", &decorations, attribute)
+                    .map(|decorations| theme.decorations = decorations),
+            );
+        }
+        if let Some(marks) = cards.decoration_marks {
+            check(
+                optional_triple("cards.decoration_marks", &marks, glyph)
+                    .map(|marks| theme.decoration_marks = marks),
+            );
+        }
+        if let Some(ascii) = cards.ascii {
+            theme.ascii = ascii;
+        }
+        if let (false, Some(name)) = (keymap_given, file.keys.layout) {
+            check(
+                Keymap::preset(&name)
+                    .map(|keymap| config.keymap = keymap)
+                    .ok_or_else(|| {
+                        fill(
+                            "error.unknown_keys",
+                            &[("name", &name), ("expected", &keymap::PRESETS.join(", "))],
+                        )
+                    }),
+            );
+        }
+        if let (false, Some(grid)) = (keymap_given, file.keys.grid) {
+            check(
+                Keymap::from_rows(grid)
+                    .map(|keymap| config.keymap = keymap)
+                    .map_err(|error| format!("keys.grid: {error}")),
+            );
+        }
+        (config, errors)
+    }
+}
+
+/// Parses a list of exactly three values, one for each value of an attribute
+fn triple<T>(
+    name: &str,
+    values: &[String],
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<[T; 3], String> {
+    let parsed = values
+        .iter()
+        .map(|value| parse(value).map_err(|error| format!("{name}: {error}")))
+        .collect::<Result<Vec<_>, _>>()?;
+    parsed
+        .try_into()
+        .map_err(|_| fill("config.count", &[("name", &name), ("count", &values.len())]))
+}
+
+/// Like `triple`, but an empty list turns the option off
+fn optional_triple<T>(
+    name: &str,
+    values: &[String],
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<[T; 3]>, String> {
+    if values.is_empty() {
+        Ok(None)
+    } else {
+        triple(name, values, parse).map(Some)
+    }
+}
+
+/// Whether a character takes up a column other than one in the terminal: wide East Asian characters and emoji,
+/// which take up two, and combining marks and zero-width characters, which take up none.
+/// Cards are laid out one column per character, so these would misalign them.
+fn is_not_one_column(c: char) -> bool {
+    matches!(
+        u32::from(c),
+        0x0300..=0x036F
+            | 0x200B..=0x200F
+            | 0x1100..=0x115F
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA000..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE00..=0xFE0F
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x1F300..=0x1F64F
+            | 0x1F900..=0x1F9FF
+            | 0x20000..=0x3FFFD
+    )
+}
+
+fn glyph(value: &str) -> Result<char, String> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_control() && !c.is_whitespace() && !is_not_one_column(c) => Ok(c),
+        _ => Err(fill("config.glyph", &[("value", &format!("{value:?}"))])),
+    }
+}
+
+fn colour(value: &str) -> Result<Color, String> {
+    let invalid = || fill("config.colour", &[("value", &format!("{value:?}"))]);
+    match value.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 => {
+            let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+            match (channel(0), channel(2), channel(4)) {
+                (Some(r), Some(g), Some(b)) => Ok(Color::Rgb { r, g, b }),
+                _ => Err(invalid()),
+            }
+        }
+        Some(_) => Err(invalid()),
+        None => Color::try_from(value).map_err(|()| invalid()),
+    }
+}
+
+fn attribute(value: &str) -> Result<Attribute, String> {
+    Ok(match value {
+        "none" | "no_underline" => Attribute::NoUnderline,
+        "underlined" => Attribute::Underlined,
+        "double_underlined" => Attribute::DoubleUnderlined,
+        "undercurled" => Attribute::Undercurled,
+        "dotted_underlined" => Attribute::Underdotted,
+        "dashed_underlined" => Attribute::Underdashed,
+        "no_bold" => Attribute::NoBold,
+        "bold" => Attribute::Bold,
+        "dim" => Attribute::Dim,
+        "italic" => Attribute::Italic,
+        "reverse" => Attribute::Reverse,
+        "crossed_out" => Attribute::CrossedOut,
+        _ => {
+            return Err(fill(
+                "config.decoration",
+                &[("value", &format!("{value:?}"))],
+            ))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Loads a config file with the given contents, written to a file with the given name
+    fn load(name: &str, contents: &str, theme: Option<Theme>) -> (Config, Vec<String>) {
+        let path = std::env::temp_dir().join(format!(
+            "rust-set-config-{name}-{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        let loaded = Config::load(&path, theme, None);
+        std::fs::remove_file(&path).unwrap();
+        loaded
+    }
+
+    #[test]
+    fn errors_give_the_line_of_the_problem() {
+        let contents = "theme = \"glyph\"\n\n[cards]\nsparkles = true\n";
+        let (_, errors) = load("line", contents, None);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("line 4:"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn the_example_keeps_the_theme_it_names() {
+        for name in PRESETS {
+            let contents = EXAMPLE.replace("theme = \"standard\"", &format!("theme = \"{name}\""));
+            let (config, errors) = load(&format!("example-{name}"), &contents, None);
+            assert!(errors.is_empty(), "{errors:?}");
+            assert_eq!(config.theme, Theme::preset(name).unwrap());
+            assert_eq!(config.keymap, Keymap::default());
+        }
+    }
+
+    #[test]
+    fn a_given_theme_replaces_the_cards_section() {
+        let contents =
+            "[cards]\nshapes = [\"a\", \"b\", \"c\"]\ncolour_marks = []\nascii = false\n";
+        let ascii = Theme::preset("ascii").unwrap();
+        let (config, errors) = load("given-theme", contents, Some(ascii.clone()));
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(config.theme, ascii);
+        let (config, _) = load("no-given-theme", contents, None);
+        assert_eq!(config.theme.letters, ['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn glyphs_are_one_column_wide() {
+        for value in ["O", "◆", "é", "λ"] {
+            assert!(glyph(value).is_ok(), "{value}");
+        }
+        for value in ["", "OO", " ", "\t", "菱", "🔷", "Ｏ", "\u{301}"] {
+            assert!(glyph(value).is_err(), "{value:?}");
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    event::KeyCode,
+    queue,
+    style::{Print as P, PrintStyledContent as PS, Stylize},
+};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    card::{Attribute, Card, ATTRIBUTES},
+    locale::{fill, text},
+    util::{read_char, say, Input},
+};
+
+/// Number of cards to choose from in each question
+pub const OPTIONS: usize = 4;
+
+/// Two cards and a choice of cards which might complete them into a set
+pub struct Question {
+    pub pair: [Card; 2],
+    pub options: Vec<Card>,
+    /// Index of the card in `options` which forms a set with `pair`
+    pub answer: usize,
+    /// The attribute in which most wrong options differ from the answer
+    pub focus: Attribute,
+}
+
+impl Question {
+    /// Deals two distinct cards. The options are the card completing the set, two cards differing from it
+    /// only in the focus attribute, and one differing from it only in another attribute.
+    pub fn new(rng: &mut impl Rng, focus: Attribute) -> Self {
+        loop {
+            let first = rng.gen_range(0..81);
+            let second = (first + rng.gen_range(1..81)) % 81;
+            let pair = [first, second].map(|tile| Card::from_tile_number(tile).unwrap());
+            let answer = pair[0].third(&pair[1]);
+            let others = ATTRIBUTES
+                .into_iter()
+                .filter(|attribute| *attribute != focus)
+                .collect::<Vec<_>>();
+            let other = *others.choose(rng).unwrap();
+            let value = |attribute: Attribute| attribute.value(&answer) as u8;
+            let mut options = vec![
+                answer.with(focus, value(focus) + 1),
+                answer.with(focus, value(focus) + 2),
+                answer.with(other, value(other) + rng.gen_range(1..3)),
+            ];
+            // a wrong option equal to one of the pair would give the answer away
+            if options.iter().any(|option| pair.contains(option)) {
+                continue;
+            }
+            let answer_index = rng.gen_range(0..OPTIONS);
+            options.insert(answer_index, answer);
+            return Self {
+                pair,
+                options,
+                answer: answer_index,
+                focus,
+            };
+        }
+    }
+
+    /// The attribute in which an option differs from the answer, or None for the answer itself
+    pub fn difference(&self, option: usize) -> Option<Attribute> {
+        let answer = &self.options[self.answer];
+        ATTRIBUTES
+            .into_iter()
+            .find(|attribute| attribute.value(answer) != attribute.value(&self.options[option]))
+    }
+}
+
+/// Results for questions focused on one attribute
+#[derive(Clone, Copy, Default, Debug)]
+pub struct AttributeStats {
+    pub questions: u32,
+    pub correct: u32,
+    /// Time taken to answer all the questions
+    pub time: Duration,
+    /// Number of wrong answers, in any question, which differed from the answer in this attribute
+    pub mistakes: u32,
+}
+
+/// Accuracy and reaction times in the drill, for each attribute in the order of the `Attribute` enum
+#[derive(Default, Debug)]
+pub struct Stats(pub [AttributeStats; 4]);
+
+impl Stats {
+    pub fn record(&mut self, question: &Question, choice: usize, time: Duration) {
+        let focus = &mut self.0[question.focus as usize];
+        focus.questions += 1;
+        focus.time += time;
+        match question.difference(choice) {
+            None => focus.correct += 1,
+            Some(attribute) => self.0[attribute as usize].mistakes += 1,
+        }
+    }
+
+    /// The attribute with the longest average time to answer, if any questions were answered
+    pub fn slowest(&self) -> Option<Attribute> {
+        ATTRIBUTES
+            .into_iter()
+            .filter(|attribute| self.0[*attribute as usize].questions > 0)
+            .max_by_key(|attribute| {
+                let stats = self.0[*attribute as usize];
+                stats.time / stats.questions
+            })
+    }
+}
+
+/// Asks "find the third card" questions until Q is pressed, then shows statistics by attribute
+pub fn play(out: &mut impl Write) -> std::io::Result<()> {
+    let mut rng = rand::thread_rng();
+    let mut stats = Stats::default();
+    say(out, "")?;
+    say(out, text("drill.intro"))?;
+    'questions: loop {
+        let focus = *ATTRIBUTES.choose(&mut rng).unwrap();
+        let question = Question::new(&mut rng, focus);
+        say(out, "")?;
+        queue!(
+            out,
+            P("  "),
+            question.pair[0].stylise(false),
+            P("  "),
+            question.pair[1].stylise(false),
+            P("  ?\r\n")
+        )?;
+        for (number, option) in (1..).zip(&question.options) {
+            queue!(out, P(format!("  {number}: ")), option.stylise(false))?;
+        }
+        say(out, "")?;
+        out.flush()?;
+        let asked = Instant::now();
+        let choice = loop {
+            match read_char(false)? {
+                Input::Key(KeyCode::Char('Q')) => break 'questions,
+                Input::Key(KeyCode::Char(key)) => {
+                    let choice = key.to_digit(10).map_or(0, |digit| digit as usize);
+                    if (1..=OPTIONS).contains(&choice) {
+                        break choice - 1;
+                    }
+                }
+                _ => {}
+            }
+        };
+        let time = asked.elapsed();
+        stats.record(&question, choice, time);
+        let seconds = format!("{:.1}s", time.as_secs_f32());
+        match question.difference(choice) {
+            None => say(
+                out,
+                &fill("drill.correct", &[("time", &seconds)])
+                    .green()
+                    .to_string(),
+            )?,
+            Some(attribute) => {
+                let answer = &question.options[question.answer];
+                let message = fill(
+                    "drill.wrong",
+                    &[
+                        ("number", &(question.answer + 1)),
+                        ("card", answer),
+                        ("attribute", &attribute),
+                    ],
+                );
+                say(out, &message.red().to_string())?;
+            }
+        }
+    }
+    show_stats(out, &stats)?;
+    queue!(out, PS(text("drill.return").dim()), P("\r\n"))?;
+    out.flush()?;
+    while !matches!(read_char(false)?, Input::Key(_)) {}
+    Ok(())
+}
+
+fn show_stats(out: &mut impl Write, stats: &Stats) -> std::io::Result<()> {
+    say(out, "")?;
+    let Some(slowest) = stats.slowest() else {
+        return say(out, text("drill.no_questions"));
+    };
+    say(out, &text("drill.stats").bold().to_string())?;
+    let header = [
+        text("drill.attribute"),
+        text("drill.accuracy"),
+        text("drill.time"),
+        text("drill.mistakes"),
+    ];
+    say(
+        out,
+        &format!(
+            "  {:<12}{:>10}{:>14}{:>10}",
+            header[0], header[1], header[2], header[3]
+        ),
+    )?;
+    for attribute in ATTRIBUTES {
+        let AttributeStats {
+            questions,
+            correct,
+            time,
+            mistakes,
+        } = stats.0[attribute as usize];
+        let (accuracy, average) = match questions {
+            0 => ("-".to_owned(), "-".to_owned()),
+            _ => (
+                format!("{correct}/{questions}"),
+                format!("{:.1}s", (time / questions).as_secs_f32()),
+            ),
+        };
+        say(
+            out,
+            &format!(
+                "  {:<12}{accuracy:>10}{average:>14}{mistakes:>10}",
+                attribute.to_string()
+            ),
+        )?;
+    }
+    say(out, &fill("drill.slowest", &[("attribute", &slowest)]))
+}
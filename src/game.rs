@@ -1,56 +1,321 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use crate::card::*;
+use crate::{
+    bitset::CardSet,
+    card::*,
+    layout::{Layout, ROWS},
+    locale::{fill, text},
+};
 use crossterm::{
-    cursor::{MoveTo, MoveToNextLine},
+    cursor::MoveTo,
     queue,
-    style::{Print as P, PrintStyledContent as PS, Stylize},
+    style::{Color, Print as P, PrintStyledContent, PrintStyledContent as PS, Stylize},
     terminal::{Clear, ClearType},
 };
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::Serialize;
+
+/// The outcome of checking three selected cards: the removed cards if they form a set, otherwise why they do not
+pub type SetResult<'a> = Result<(Card, Card, Card), Mismatch<'a>>;
+
+/// Three selected cards which do not form a set, left in place, in the order they appear on the table
+pub struct Mismatch<'a> {
+    pub cards: (&'a Card, &'a Card, &'a Card),
+    /// The attributes which are neither all the same nor all different, in the order of `ATTRIBUTES`
+    pub broken: Vec<Attribute>,
+}
+
+impl Mismatch<'_> {
+    /// Lists each broken attribute with its values, e.g. "number (one, one, two), colour (red, red, green)"
+    pub fn describe(&self) -> String {
+        let (a, b, c) = self.cards;
+        self.broken
+            .iter()
+            .map(|attribute| {
+                let [a, b, c] = [a, b, c].map(|card| attribute.describe(card));
+                format!("{attribute} ({a}, {b}, {c})")
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// The most cards that can be shown at once: the keybind table has 3 rows of 7 keys, and any 21 cards contain a set
+pub const MAX_SHOWN: usize = 21;
+/// Length of a time-attack game unless another is chosen
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(180);
+
+/// Table size and dealing rules for a game
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GameConfig {
+    /// Number of cards the table is topped up to after a set is found
+    pub table_size: usize,
+    /// Number of extra cards drawn at a time when the table has no set
+    pub extra_draw: usize,
+    /// Whether extra cards are replaced after a set is found, instead of letting the table shrink back to `table_size`
+    pub refill_extras: bool,
+    /// Tournament-style play: extra cards are only drawn when the player calls "no set", see `Game::call_no_set`
+    pub call_no_set: bool,
+    /// Time attack: the game ends once this much time has passed instead of when the deck runs out,
+    /// and found sets are shuffled back into the pile when it runs low
+    pub time_limit: Option<Duration>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            table_size: 12,
+            extra_draw: 3,
+            refill_extras: false,
+            call_no_set: false,
+            time_limit: None,
+        }
+    }
+}
+
+impl GameConfig {
+    /// A fast-paced 9-card table
+    pub fn speed() -> Self {
+        Self {
+            table_size: 9,
+            ..Self::default()
+        }
+    }
+
+    /// A 15-card table where extra cards stay on the table
+    pub fn relaxed() -> Self {
+        Self {
+            table_size: 15,
+            refill_extras: true,
+            ..Self::default()
+        }
+    }
+
+    /// Checks that the table fits in the 3-row layout and can never grow past `MAX_SHOWN` cards
+    pub fn validate(&self) -> Result<(), String> {
+        if self.table_size == 0 || !self.table_size.is_multiple_of(3) || self.table_size > MAX_SHOWN
+        {
+            return Err(fill(
+                "error.table_size",
+                &[("max", &MAX_SHOWN), ("size", &self.table_size)],
+            ));
+        }
+        if self.extra_draw == 0 || !self.extra_draw.is_multiple_of(3) {
+            return Err(fill("error.extra_draw", &[("extra", &self.extra_draw)]));
+        }
+        if !(MAX_SHOWN - self.table_size).is_multiple_of(self.extra_draw) {
+            return Err(fill(
+                "error.table_overflow",
+                &[
+                    ("extra", &self.extra_draw),
+                    ("size", &self.table_size),
+                    ("max", &MAX_SHOWN),
+                ],
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Tally of the player's successes and mistakes
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Score {
+    pub sets: u32,
+    pub invalid_sets: u32,
+    pub correct_calls: u32,
+    pub wrong_calls: u32,
+    pub hints: u32,
+}
+
+impl Score {
+    /// One point per set and correct "no set" call, minus one per wrong call
+    pub fn points(&self) -> i64 {
+        i64::from(self.sets) + i64::from(self.correct_calls) - i64::from(self.wrong_calls)
+    }
+
+    /// Selections which were not sets and wrong "no set" calls
+    pub fn mistakes(&self) -> u32 {
+        self.invalid_sets + self.wrong_calls
+    }
+}
+
+/// Something the player did, recorded so that a game can be replayed
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Move {
+    /// `Game::select_card`
+    Toggle {
+        card: usize,
+    },
+    /// `Game::deselect_card`, or `Game::pop_last` deselecting this card
+    Deselect {
+        card: usize,
+    },
+    CallNoSet,
+    Hint,
+}
+
+/// The outcome of calling "no set"
+pub enum NoSetCall {
+    /// There was no set; extra cards have been drawn, or the game has ended if the pile is empty
+    Correct,
+    /// There was a set, at the given indices, which is now revealed on the table
+    Wrong([usize; 3]),
+}
+
+/// A change to the table, queued for the interface to animate
+#[derive(Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Cards were dealt to these indices, either to replace a found set or, if `extra`, because there was no set
+    Dealt { indices: Vec<usize>, extra: bool },
+    /// A set was found and taken off the table; the cards were at these indices
+    Found {
+        cards: [Card; 3],
+        indices: [usize; 3],
+    },
+    /// The cards selected at these indices were not a set
+    Invalid { indices: [usize; 3] },
+}
 
 /// Represents the state of a set Game
 pub struct Game {
+    pub started: Instant,
     pub ended: Option<Instant>,
+    pub score: Score,
+    /// Time since the start of the game at which each set was found
+    pub splits: Vec<Duration>,
+    /// The seed the deck was shuffled with, if the game was dealt by `Game::from_seed`
+    pub seed: Option<u64>,
+    config: GameConfig,
     selection: Vec<usize>,
+    /// Indices of cards shown to the player after a hint, or of a set after a wrong "no set" call
+    revealed: Vec<usize>,
     pile: Vec<Card>,
     shown: Vec<Card>,
+    /// Cards removed in found sets, in the order they were found
+    discards: Vec<Card>,
+    /// Shuffles discards back into the pile in time attack
+    rng: StdRng,
+    /// Every move made, with the time since the start of the game
+    moves: Vec<(Duration, Move)>,
+    /// Changes to the table not yet taken by `Game::take_events`
+    events: Vec<Event>,
 }
 
 impl Game {
-    pub fn new(card_id: impl Iterator<Item = u8>) -> Self {
-        let mut list = card_id
+    pub fn new(card_id: impl Iterator<Item = u8>, config: GameConfig) -> Self {
+        Self::deal(card_id, config, StdRng::from_entropy(), None)
+    }
+
+    /// Deals from a deck shuffled with the given seed. Games with the same seed and config deal the same cards
+    /// as long as the same moves are made.
+    pub fn from_seed(seed: u64, config: GameConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut card_ids = (0u8..81).collect::<Vec<_>>();
+        card_ids.shuffle(&mut rng);
+        Self::deal(card_ids.into_iter(), config, rng, Some(seed))
+    }
+
+    fn deal(
+        card_id: impl Iterator<Item = u8>,
+        config: GameConfig,
+        rng: StdRng,
+        seed: Option<u64>,
+    ) -> Self {
+        let pile = card_id
             .map(|num| Card::from_tile_number(num).unwrap())
             .collect::<Vec<Card>>();
         let mut game = Game {
-            pile: list.split_off(12),
-            shown: list,
+            pile,
+            shown: Vec::new(),
+            discards: Vec::new(),
+            config,
             selection: Vec::new(),
+            revealed: Vec::new(),
+            score: Score::default(),
+            splits: Vec::new(),
+            seed,
+            rng,
+            moves: Vec::new(),
+            events: Vec::new(),
+            started: Instant::now(),
             ended: None,
         };
         game.draw_as_needed();
-        return game;
+        // the first deal is not animated
+        game.events.clear();
+        game
     }
 
-    /// Repeatedly draw cards until `Self::check_need_more()` returns false.
+    /// Tops the table up to the configured table size, then draws extra cards until there is a set.
+    /// When calling "no set" is enabled, extra cards are left for `Self::call_no_set` to draw.
     fn draw_as_needed(&mut self) {
+        self.recycle_discards();
         let shown_count = self.shown.len();
-        if shown_count < 12 {
+        let table_size = self.config.table_size;
+        if shown_count < table_size && !self.pile.is_empty() {
             self.pile
-                .drain(0..((12 - shown_count).min(self.pile.len())))
+                .drain(0..((table_size - shown_count).min(self.pile.len())))
                 .for_each(|card| self.shown.push(card));
+            self.events.push(Event::Dealt {
+                indices: (shown_count..self.shown.len()).collect(),
+                extra: false,
+            });
         }
         while !Game::has_set(self.shown.iter()) {
-            if self.pile.len() == 0 {
+            self.recycle_discards();
+            if self.pile.is_empty() {
                 self.ended = Some(std::time::Instant::now());
                 break;
+            } else if self.config.call_no_set {
+                break;
             } else {
-                self.pile.drain(0..3).for_each(|card| self.shown.push(card));
+                self.draw_extra();
             }
         }
     }
 
-    /// Checks if the provided list of cards is a valid shown pile (at least 12 cards, 1 set)
-    fn has_set<'a>(cards: impl IntoIterator<Item = &'a Card>) -> bool {
+    fn draw_extra(&mut self) {
+        self.recycle_discards();
+        let extra_draw = self.config.extra_draw.min(self.pile.len());
+        if extra_draw == 0 {
+            return;
+        }
+        let shown_count = self.shown.len();
+        self.pile
+            .drain(0..extra_draw)
+            .for_each(|card| self.shown.push(card));
+        self.events.push(Event::Dealt {
+            indices: (shown_count..self.shown.len()).collect(),
+            extra: true,
+        });
+    }
+
+    /// In a time-attack game, shuffles the discards back into the pile once it holds fewer cards than a full table,
+    /// so the game never runs out of cards
+    fn recycle_discards(&mut self) {
+        let low = self.pile.len() < self.config.table_size.max(self.config.extra_draw);
+        if self.config.time_limit.is_some() && low {
+            self.pile.append(&mut self.discards);
+            self.pile.shuffle(&mut self.rng);
+        }
+    }
+
+    /// Finds the indices of a set among the shown cards, if there is one
+    fn find_set(&self) -> Option<[usize; 3]> {
+        let tiles = self.shown.iter().collect::<CardSet>();
+        let position = |tile| {
+            self.shown
+                .iter()
+                .position(|card| card.to_tile_number() == tile)
+                .unwrap()
+        };
+        tiles.sets().next().map(|set| set.map(position))
+    }
+
+    /// Checks if the provided list of cards is a valid shown pile (at least 12 cards, 1 set).
+    /// See `CardSet::has_set` for a faster alternative when analysing many tables.
+    pub fn has_set<'a>(cards: impl IntoIterator<Item = &'a Card>) -> bool {
         let cards = cards.into_iter().collect::<Vec<_>>();
         let len = cards.len();
         if len == 0 {
@@ -59,23 +324,27 @@ impl Game {
         for i in 0..len - 1 {
             for j in (i + 1)..len {
                 for k in (0..i).chain(j..len) {
-                    if cards[i].third(&cards[j]) == *cards[k] {
+                    if cards[i].third(cards[j]) == *cards[k] {
                         return true;
                     }
                 }
             }
         }
-        return false;
+        false
     }
 }
 
 impl Game {
     /// Toggles the select state for card index.
     /// Returns Err(()) if index out of bounds; otherwise, returns Ok of a tuple, containing a reference to modified card and a boolean, which is true if the card is now selected.
+    #[allow(clippy::result_unit_err)]
     pub fn select_card(&mut self, index: usize) -> Result<(&Card, bool), ()> {
+        self.revealed.clear();
         if index >= self.shown.len() {
-            Err(())
-        } else if let Some(index_index) = self.selection.iter().position(|x| *x == index) {
+            return Err(());
+        }
+        self.record(Move::Toggle { card: index });
+        if let Some(index_index) = self.selection.iter().position(|x| *x == index) {
             Ok((&self.shown[self.selection.swap_remove(index_index)], false))
         } else {
             self.selection.push(index);
@@ -83,15 +352,24 @@ impl Game {
         }
     }
 
+    /// Deselects the card at index, returning it if it was selected
+    pub fn deselect_card(&mut self, index: usize) -> Option<&Card> {
+        self.revealed.clear();
+        let index_index = self.selection.iter().position(|x| *x == index)?;
+        self.record(Move::Deselect { card: index });
+        Some(&self.shown[self.selection.swap_remove(index_index)])
+    }
+
     pub fn pop_last(&mut self) -> Option<&Card> {
-        self.selection.pop().map(|index| &self.shown[index])
+        self.revealed.clear();
+        let index = self.selection.pop()?;
+        self.record(Move::Deselect { card: index });
+        Some(&self.shown[index])
     }
 
     /// Checks if selected cards form a set, and replaces cards where needed.
     /// Returns None if not enough cards are selected; returns Some(Ok()) containing the selected cards if they are a set, or Some(None()) containing an immutable reference to the selected cards that are not a set.
-    pub fn check_selected_set(
-        &mut self,
-    ) -> Option<Result<(Card, Card, Card), (&Card, &Card, &Card)>> {
+    pub fn check_selected_set(&mut self) -> Option<SetResult<'_>> {
         if self.selection.len() < 3 {
             return None;
         }
@@ -99,16 +377,25 @@ impl Game {
         let index1 = self.selection[0];
         let index2 = self.selection[1];
         let index3 = self.selection[2];
-        let ret: Result<(Card, Card, Card), (&Card, &Card, &Card)>;
+        let ret: SetResult;
         if self.shown[index1].third(&self.shown[index2]) == self.shown[index3] {
+            self.events.push(Event::Found {
+                cards: [self.shown[index1], self.shown[index2], self.shown[index3]],
+                indices: [index1, index2, index3],
+            });
+            self.recycle_discards();
             let post_removal_cards = (0..self.shown.len())
-                .filter(|x| self.selection.contains(x))
+                .filter(|x| !self.selection.contains(x))
                 .map(|x| &self.shown[x])
                 .collect::<Vec<_>>();
             let has_set = Game::has_set(post_removal_cards);
             let shown_len = self.shown.len();
             let pile_len = self.pile.len();
-            if (has_set && shown_len > 12) || pile_len < 3 {
+            // in "no set" call mode, extra cards are always removed so the remaining table gives nothing away
+            let shrink = (has_set || self.config.call_no_set)
+                && shown_len > self.config.table_size
+                && !self.config.refill_extras;
+            if shrink || pile_len < 3 {
                 ret = Ok((
                     self.shown.swap_remove(index3),
                     self.shown.swap_remove(index2),
@@ -124,41 +411,438 @@ impl Game {
                     std::mem::replace(&mut self.shown[index2], drawn_cards.pop().unwrap()),
                     std::mem::replace(&mut self.shown[index1], drawn_cards.pop().unwrap()),
                 ));
+                self.events.push(Event::Dealt {
+                    indices: vec![index1, index2, index3],
+                    extra: false,
+                });
                 self.draw_as_needed();
             }
+            if let Ok((card1, card2, card3)) = &ret {
+                self.discards.extend([*card3, *card2, *card1]);
+            }
             self.selection.clear();
+            self.score.sets += 1;
+            self.splits.push(self.started.elapsed());
         } else {
-            ret = Err((
-                &self.shown[index3],
-                &self.shown[index2],
+            self.score.invalid_sets += 1;
+            self.events.push(Event::Invalid {
+                indices: [index1, index2, index3],
+            });
+            let cards = (
                 &self.shown[index1],
-            ));
+                &self.shown[index2],
+                &self.shown[index3],
+            );
+            ret = Err(Mismatch {
+                broken: Card::broken_attributes([cards.0, cards.1, cards.2]),
+                cards,
+            });
             self.selection.clear();
         }
         Some(ret)
     }
 
-    pub fn print(&self, out: &mut impl std::io::Write) -> Result<(), std::io::Error> {
-        let table_width = self.shown.len() / 3;
-        queue!(out, MoveTo(0, 1))?;
-        for row in 0usize..3 {
-            queue!(out, Clear(ClearType::CurrentLine))?;
-            for column in 0usize..table_width {
-                queue!(
-                    out,
-                    self.shown[row + column * 3]
-                        .stylise(self.selection.contains(&(row + column * 3))),
-                )?;
-                if table_width - column != 1 {
-                    queue!(out, P("  |  "))?;
+    /// Handles the player claiming there is no set among the shown cards.
+    /// A correct call scores a point and draws extra cards; a wrong call loses a point and reveals a set.
+    pub fn call_no_set(&mut self) -> NoSetCall {
+        self.record(Move::CallNoSet);
+        self.selection.clear();
+        match self.find_set() {
+            Some(set) => {
+                self.score.wrong_calls += 1;
+                self.revealed = set.to_vec();
+                NoSetCall::Wrong(set)
+            }
+            None => {
+                self.score.correct_calls += 1;
+                self.revealed.clear();
+                self.recycle_discards();
+                if self.pile.is_empty() {
+                    self.ended = Some(std::time::Instant::now());
+                } else {
+                    self.draw_extra();
                 }
+                NoSetCall::Correct
+            }
+        }
+    }
+
+    /// Reveals a card which is part of a set on the table, preferring one that is not selected.
+    /// Returns its index, or None if there is no set. There are no hints when calling "no set" is enabled,
+    /// as knowing whether there is a set would make every call safe.
+    pub fn hint(&mut self) -> Option<usize> {
+        if self.config.call_no_set {
+            return None;
+        }
+        self.record(Move::Hint);
+        let set = self.find_set()?;
+        let index = set
+            .into_iter()
+            .find(|index| !self.selection.contains(index))
+            .unwrap_or(set[0]);
+        self.score.hints += 1;
+        self.revealed = vec![index];
+        Some(index)
+    }
+
+    /// Takes the changes to the table since the last call, oldest first
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn record(&mut self, action: Move) {
+        self.moves.push((self.started.elapsed(), action));
+    }
+
+    /// Every move made so far, with the time since the start of the game
+    pub fn moves(&self) -> &[(Duration, Move)] {
+        &self.moves
+    }
+
+    /// Ends a time-attack game once its time is up. Returns whether the game has ended, for any reason.
+    pub fn check_time_limit(&mut self) -> bool {
+        if let (None, Some(limit)) = (self.ended, self.config.time_limit) {
+            if self.started.elapsed() >= limit {
+                self.ended = Some(self.started + limit);
+                self.selection.clear();
             }
-            queue!(out, MoveToNextLine(1))?;
         }
+        self.ended.is_some()
+    }
+
+    /// Time left before a time-attack game ends, or None if the game has no time limit
+    pub fn time_left(&self) -> Option<Duration> {
+        let now = self.ended.unwrap_or_else(Instant::now);
+        let limit = self.config.time_limit?;
+        Some(limit.saturating_sub(now - self.started))
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// The cards on the table, in the order they are numbered
+    pub fn shown(&self) -> &[Card] {
+        &self.shown
+    }
+
+    /// Number of cards not yet dealt
+    pub fn pile_count(&self) -> usize {
+        self.pile.len()
+    }
+
+    /// Number of cards on the table
+    pub fn shown_count(&self) -> usize {
+        self.shown.len()
+    }
+
+    /// Prints the table and pile count at the positions given by the layout.
+    /// The card under the keyboard cursor, if any, is printed in reverse video.
+    pub fn print(
+        &self,
+        out: &mut impl std::io::Write,
+        layout: &Layout,
+        cursor: Option<usize>,
+    ) -> Result<(), std::io::Error> {
+        print_table(out, layout, &self.shown, cursor, |index| {
+            if self.revealed.contains(&index) {
+                Some(Color::DarkYellow)
+            } else if self.selection.contains(&index) {
+                Some(Color::DarkGrey)
+            } else {
+                None
+            }
+        })?;
+        queue!(out, MoveTo(0, layout.pile))?;
         queue!(
             out,
             PS(self.pile.len().to_string().bold()),
-            P(" cards not yet shown")
-        )
+            P(" "),
+            P(text("game.pile")),
+            Clear(ClearType::UntilNewLine)
+        )?;
+        if self.config.call_no_set {
+            queue!(
+                out,
+                P(", "),
+                P(text("game.score")),
+                PS(self.score.points().to_string().bold())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Clears the table area and draws cards at their places in the layout, with the background chosen for each index
+/// and the card under the cursor reversed
+pub fn print_table(
+    out: &mut impl std::io::Write,
+    layout: &Layout,
+    cards: &[Card],
+    cursor: Option<usize>,
+    background: impl Fn(usize) -> Option<Color>,
+) -> Result<(), std::io::Error> {
+    let separator = layout.separator();
+    for row in 1..layout.pile {
+        queue!(out, MoveTo(0, row), Clear(ClearType::CurrentLine))?;
+    }
+    for (index, card) in cards.iter().enumerate().take(layout.cards.len()) {
+        print_card(
+            out,
+            layout,
+            index,
+            card,
+            background(index),
+            cursor == Some(index),
+        )?;
+        if !layout.big_cards && index + ROWS < cards.len() {
+            queue!(out, P(&separator))?;
+        }
+    }
+    Ok(())
+}
+
+/// Draws a card at the place of the given index in the layout, on a background and reversed if it is under the cursor.
+/// Nothing is drawn if the layout has no place for the index.
+pub fn print_card(
+    out: &mut impl std::io::Write,
+    layout: &Layout,
+    index: usize,
+    card: &Card,
+    background: Option<Color>,
+    reversed: bool,
+) -> Result<(), std::io::Error> {
+    let Some(rect) = layout.cards.get(index) else {
+        return Ok(());
+    };
+    let at_cursor = |PrintStyledContent(styled)| {
+        PrintStyledContent(if reversed { styled.reverse() } else { styled })
+    };
+    if layout.big_cards {
+        for (line, styled) in (rect.y..).zip(card.stylise_big(background)) {
+            queue!(out, MoveTo(rect.x, line), at_cursor(styled))?;
+        }
+        return Ok(());
+    }
+    let styled = match background {
+        Some(background) => card.stylise_on(background),
+        None => card.stylise(false),
+    };
+    queue!(out, MoveTo(rect.x, rect.y), at_cursor(styled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deals the given tiles first, then the rest of the deck in order
+    fn game_with(first: &[u8], config: GameConfig) -> Game {
+        let rest = (0..81).filter(|tile| !first.contains(tile));
+        Game::new(first.iter().copied().chain(rest), config)
+    }
+
+    fn select(game: &mut Game, indices: [usize; 3]) -> bool {
+        indices.into_iter().for_each(|index| {
+            game.select_card(index).unwrap();
+        });
+        game.check_selected_set().unwrap().is_ok()
+    }
+
+    fn time_attack(limit: u64) -> Game {
+        let config = GameConfig {
+            time_limit: Some(Duration::from_secs(limit)),
+            ..GameConfig::default()
+        };
+        Game::from_seed(0, config)
+    }
+
+    #[test]
+    fn time_attack_recycles_found_sets() {
+        let mut game = time_attack(60);
+        // far more sets than the 27 in a deck
+        for _ in 0..200 {
+            let set = game.find_set().expect("the table always has a set");
+            assert!(select(&mut game, set));
+            assert!(game.ended.is_none());
+            assert_eq!(
+                game.pile_count() + game.shown_count() + game.discards.len(),
+                81
+            );
+        }
+        assert_eq!(game.score.sets, 200);
+    }
+
+    #[test]
+    fn time_attack_ends_at_the_time_limit() {
+        let mut game = time_attack(10);
+        assert!(!game.check_time_limit());
+        game.select_card(0).unwrap();
+        game.started -= Duration::from_secs(11);
+        assert!(game.check_time_limit());
+        assert_eq!(game.ended, Some(game.started + Duration::from_secs(10)));
+        assert!(game.selection.is_empty());
+    }
+
+    #[test]
+    fn time_left_stops_at_zero() {
+        let mut game = time_attack(10);
+        assert!(game.time_left().unwrap() <= Duration::from_secs(10));
+        game.started -= Duration::from_secs(20);
+        assert_eq!(game.time_left(), Some(Duration::ZERO));
+        game.check_time_limit();
+        assert_eq!(game.time_left(), Some(Duration::ZERO));
+        assert_eq!(Game::from_seed(0, GameConfig::default()).time_left(), None);
+    }
+
+    #[test]
+    fn there_are_no_hints_when_calling_no_set() {
+        let config = GameConfig {
+            call_no_set: true,
+            ..GameConfig::default()
+        };
+        // seed 0 has a set on the first table, but a hint would still tell a safe call from a wrong one
+        let mut game = Game::from_seed(0, config);
+        assert!(game.find_set().is_some());
+        assert_eq!(game.hint(), None);
+        assert!(game.revealed.is_empty());
+        assert!(game.moves().is_empty());
+    }
+
+    /// 12 cards with no set, then the rest of the deck in order, starting with 2, 5 and 6
+    const NO_SET: [u8; 12] = [0, 1, 3, 4, 9, 10, 12, 13, 27, 28, 32, 35];
+
+    fn calling_no_set() -> GameConfig {
+        GameConfig {
+            call_no_set: true,
+            ..GameConfig::default()
+        }
+    }
+
+    #[test]
+    fn correct_no_set_calls_score_and_deal_extra_cards() {
+        let mut game = game_with(&NO_SET, calling_no_set());
+        assert_eq!(game.shown_count(), 12);
+        let pile = game.pile_count();
+        assert!(matches!(game.call_no_set(), NoSetCall::Correct));
+        assert_eq!(game.score.points(), 1);
+        assert_eq!(game.score.correct_calls, 1);
+        assert_eq!(game.shown_count(), 15);
+        assert_eq!(game.pile_count(), pile - 3);
+        assert!(game.ended.is_none());
+    }
+
+    #[test]
+    fn wrong_no_set_calls_lose_a_point_and_reveal_a_set() {
+        let mut game = Game::from_seed(0, calling_no_set());
+        let shown = game.shown_count();
+        let NoSetCall::Wrong([a, b, c]) = game.call_no_set() else {
+            panic!("the first table of seed 0 has a set");
+        };
+        assert_eq!(game.score.points(), -1);
+        assert_eq!(game.score.mistakes(), 1);
+        assert_eq!(game.revealed, [a, b, c]);
+        let cards = game.shown();
+        assert!(cards[a].third(&cards[b]) == cards[c]);
+        assert_eq!(game.shown_count(), shown);
+    }
+
+    #[test]
+    fn the_table_shrinks_back_after_a_set_when_calling_no_set() {
+        let mut game = game_with(&NO_SET, calling_no_set());
+        game.call_no_set();
+        assert_eq!(game.shown_count(), 15);
+        // 0, 3 and 6, the last of the extra cards
+        assert!(select(&mut game, [0, 2, 14]));
+        assert_eq!(game.shown_count(), 12);
+        assert_eq!(game.score.points(), 2);
+    }
+
+    #[test]
+    fn found_sets_are_queued_with_the_cards_dealt_in_their_place() {
+        let mut game = game_with(&NO_SET, GameConfig::default());
+        assert!(
+            game.take_events().is_empty(),
+            "the first deal is not animated"
+        );
+        let cards = [0, 2, 14].map(|index| game.shown()[index]);
+        assert!(select(&mut game, [14, 0, 2]));
+        let events = game.take_events();
+        assert!(
+            events[..2]
+                == [
+                    Event::Found {
+                        cards,
+                        indices: [0, 2, 14]
+                    },
+                    Event::Dealt {
+                        indices: vec![0, 2, 14],
+                        extra: false
+                    }
+                ]
+        );
+        assert!(events[2..]
+            .iter()
+            .all(|event| matches!(event, Event::Dealt { extra: true, .. })));
+        assert!(game.take_events().is_empty());
+    }
+
+    #[test]
+    fn invalid_selections_are_queued() {
+        let mut game = game_with(&NO_SET, GameConfig::default());
+        assert!(!select(&mut game, [2, 0, 1]));
+        assert!(game.take_events() == [Event::Invalid { indices: [0, 1, 2] }]);
+    }
+
+    #[test]
+    fn extra_cards_are_queued() {
+        let mut game = game_with(&NO_SET, calling_no_set());
+        game.call_no_set();
+        let extra = Event::Dealt {
+            indices: vec![12, 13, 14],
+            extra: true,
+        };
+        assert!(game.take_events() == [extra]);
+        // a wrong call changes nothing on the table
+        game.call_no_set();
+        assert!(game.take_events().is_empty());
+    }
+
+    #[test]
+    fn presets_are_valid() {
+        for config in [
+            GameConfig::default(),
+            GameConfig::speed(),
+            GameConfig::relaxed(),
+        ] {
+            assert_eq!(config.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        let invalid = [(0, 3), (10, 3), (24, 3), (12, 0), (12, 4), (12, 6), (15, 9)];
+        for (table_size, extra_draw) in invalid {
+            let config = GameConfig {
+                table_size,
+                extra_draw,
+                ..GameConfig::default()
+            };
+            assert!(config.validate().is_err(), "{config:?}");
+        }
+    }
+
+    #[test]
+    fn extra_cards_stay_when_the_rest_of_the_table_has_no_set() {
+        // the first 12 cards have no set, so 3 extra are dealt; 0, 3 and 6 at indices 0, 2 and 14 are a set,
+        // and the other 12 cards still have no set once it is taken
+        let mut game = game_with(&NO_SET, GameConfig::default());
+        assert_eq!(game.shown_count(), 15);
+        let before = game.shown().to_vec();
+        assert!(select(&mut game, [0, 2, 14]));
+        // the set is replaced in place rather than the table shrinking, which would move cards around
+        assert!(game.shown_count() >= 15);
+        for index in (0..15).filter(|index| ![0, 2, 14].contains(index)) {
+            assert!(game.shown()[index] == before[index], "card {index} moved");
+        }
     }
 }
@@ -0,0 +1,154 @@
+use std::sync::OnceLock;
+
+use crate::{
+    layout::ROWS,
+    locale::{fill, text},
+};
+
+static ACTIVE: OnceLock<Keymap> = OnceLock::new();
+
+/// Number of keys in each row of the grid
+pub const COLUMNS: usize = 7;
+
+/// Keys which always have their own meaning and cannot select cards
+pub static RESERVED: [char; 7] = ['Q', 'N', 'H', 'J', 'K', 'L', '?'];
+
+/// Names accepted by `Keymap::preset`
+pub static PRESETS: [&str; 5] = ["qwerty", "qwertz", "azerty", "dvorak", "colemak"];
+
+/// The grid of keys used to select cards. `grid[row][column]` selects the card drawn at that row and column.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Keymap {
+    pub grid: [[char; COLUMNS]; ROWS],
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_rows(["qwertyu", "asdfghj", "zxcvbnm"]).unwrap()
+    }
+}
+
+impl Keymap {
+    /// Builds a keymap from three rows of seven keys, checking that every key is distinct and not reserved
+    pub fn from_rows<S: AsRef<str>>(rows: [S; ROWS]) -> Result<Self, String> {
+        let mut grid = [[' '; COLUMNS]; ROWS];
+        for (row, keys) in rows.iter().enumerate() {
+            let keys = keys.as_ref().chars().collect::<Vec<_>>();
+            grid[row] = keys.clone().try_into().map_err(|_| {
+                fill(
+                    "keys.row_length",
+                    &[
+                        ("row", &(row + 1)),
+                        ("count", &keys.len()),
+                        ("expected", &COLUMNS),
+                    ],
+                )
+            })?;
+        }
+        let keys = grid.as_flattened();
+        for (i, key) in keys.iter().enumerate() {
+            if RESERVED.contains(key) || key.is_whitespace() || key.is_control() {
+                return Err(fill("keys.unusable", &[("key", &format!("{key:?}"))]));
+            }
+            if keys[..i].contains(key) {
+                return Err(fill("keys.duplicate", &[("key", &format!("{key:?}"))]));
+            }
+        }
+        Ok(Self { grid })
+    }
+
+    /// Looks up a built-in layout by name. Each uses the keys in the same physical positions as the QWERTY grid.
+    pub fn preset(name: &str) -> Option<Self> {
+        let rows = match name {
+            "qwerty" => ["qwertyu", "asdfghj", "zxcvbnm"],
+            "qwertz" => ["qwertzu", "asdfghj", "yxcvbnm"],
+            "azerty" => ["azertyu", "qsdfghj", "wxcvbn,"],
+            "dvorak" => ["',.pyfg", "aoeuidh", ";qjkxbm"],
+            "colemak" => ["qwfpgjl", "arstdhn", "zxcvbkm"],
+            _ => return None,
+        };
+        Some(Self::from_rows(rows).unwrap())
+    }
+
+    /// The keymap used to select cards. Defaults to QWERTY if none was set.
+    pub fn active() -> &'static Self {
+        ACTIVE.get_or_init(Self::default)
+    }
+
+    /// Sets the keymap used to select cards. Has no effect once the keymap has been used.
+    pub fn set_active(keymap: Self) {
+        let _ = ACTIVE.set(keymap);
+    }
+
+    /// Index of the card selected by a key. Cards fill the table column by column.
+    pub fn index_of(&self, key: char) -> Option<usize> {
+        self.grid.iter().enumerate().find_map(|(row, keys)| {
+            let column = keys.iter().position(|k| *k == key)?;
+            Some(row + column * ROWS)
+        })
+    }
+
+    /// The key which selects the card at index
+    pub fn key_for(&self, index: usize) -> char {
+        self.grid[index % ROWS][index / ROWS]
+    }
+
+    /// The keybind table shown during the game and in the tutorial, one line per row of keys,
+    /// with the cursor keys only on screens which have a cursor
+    pub fn keybinds(&self, cursor: bool) -> String {
+        let cursor = cursor.then(|| format!("  {}", text("keys.cursor")));
+        self.grid
+            .iter()
+            .map(|keys| {
+                keys.iter()
+                    .fold(String::from(" "), |line, key| format!("{line} {key}"))
+            })
+            .chain(cursor)
+            .chain([format!("  {}", text("keys.quit"))])
+            .collect::<Vec<_>>()
+            .join("\n\r")
+    }
+
+    /// Width and number of lines of the keybind table
+    pub fn keybinds_size(&self, cursor: bool) -> (u16, u16) {
+        let keybinds = self.keybinds(cursor);
+        let width = keybinds
+            .lines()
+            .map(|line| line.trim_end_matches('\r').chars().count())
+            .max()
+            .unwrap_or(0);
+        (width as u16, keybinds.lines().count() as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_have_21_distinct_usable_keys() {
+        for name in PRESETS {
+            let keymap = Keymap::preset(name).unwrap();
+            let keys = keymap.grid.as_flattened();
+            assert_eq!(keys.len(), 21);
+            for (i, key) in keys.iter().enumerate() {
+                assert!(!RESERVED.contains(key), "{name}: {key:?} is reserved");
+                assert!(!keys[..i].contains(key), "{name}: {key:?} is repeated");
+            }
+        }
+        assert!(Keymap::preset("bépo").is_none());
+    }
+
+    #[test]
+    fn invalid_grids_are_rejected() {
+        for rows in [
+            ["qwertyu", "asdfghj", "zxcvbn"],
+            ["qwertyu", "asdfghj", "zxcvbnq"],
+            ["qwertyu", "asdfghj", "zxcvbn?"],
+            ["qwertyu", "asdfghj", "zxcvbnN"],
+            ["qwertyu", "asdfghj", "zxcvbn "],
+        ] {
+            assert!(Keymap::from_rows(rows).is_err(), "{rows:?}");
+        }
+    }
+}
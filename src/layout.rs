@@ -0,0 +1,269 @@
+use crate::{
+    card::{BIG_HEIGHT, BIG_WIDTH},
+    game::MAX_SHOWN,
+    locale::{text, Locale},
+    theme::Theme,
+};
+
+/// Number of rows cards are laid out in; cards fill the table column by column
+pub const ROWS: usize = 3;
+/// Widest gap between two columns of cards; narrower terminals get narrower gaps
+pub const MAX_GAP: u16 = 5;
+/// Width of the timer, e.g. "00:01:23.456s"
+pub const TIMER_WIDTH: u16 = 13;
+/// Timer, 3 rows of cards, pile count, a blank line and the last input
+pub const MIN_HEIGHT: u16 = 7;
+
+/// Width of the longer of the "Time elapsed: 00:00:00.000s" and "Time left: 00:00:00.000s" lines in a language
+pub fn min_width(locale: &Locale) -> u16 {
+    let label = |key| locale.text(key).chars().count() as u16;
+    label("game.time_elapsed").max(label("game.time_left")) + TIMER_WIDTH
+}
+
+/// How cards are drawn on the table
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum CardSize {
+    /// Big cards if a full table of them fits in the terminal, otherwise compact cards
+    #[default]
+    Auto,
+    /// 3-character cards, see `Card::stylise`
+    Compact,
+    /// Boxed cards, see `Card::stylise_big`
+    Big,
+}
+
+impl CardSize {
+    /// The smallest terminal on which a full table of cards of this size fits
+    pub fn min_size(self) -> (u16, u16) {
+        match self {
+            Self::Auto | Self::Compact => (min_width(Locale::active()), MIN_HEIGHT),
+            Self::Big => {
+                let columns = MAX_SHOWN.div_ceil(ROWS) as u16;
+                (
+                    columns * (BIG_WIDTH + 1) - 1,
+                    MIN_HEIGHT - ROWS as u16 + ROWS as u16 * BIG_HEIGHT,
+                )
+            }
+        }
+    }
+}
+
+/// A direction to move the keyboard cursor in
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Index of the card next to `index` in the given direction on a table of `card_count` cards.
+/// The cursor stays put at the edges of the table.
+pub fn neighbour(index: usize, card_count: usize, direction: Direction) -> usize {
+    let row = index % ROWS;
+    let next = match direction {
+        Direction::Up if row > 0 => index - 1,
+        Direction::Down if row + 1 < ROWS => index + 1,
+        Direction::Left if index >= ROWS => index - ROWS,
+        Direction::Right => index + ROWS,
+        _ => index,
+    };
+    if next < card_count {
+        next
+    } else {
+        index
+    }
+}
+
+/// A rectangle on the terminal, in character cells
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rect {
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        (self.x..self.x + self.width).contains(&column)
+            && (self.y..self.y + self.height).contains(&row)
+    }
+}
+
+/// Positions of everything on the game screen, computed from the terminal size
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Layout {
+    pub width: u16,
+    pub height: u16,
+    /// Where the elapsed time is printed, after the "Time elapsed: " label
+    pub timer: (u16, u16),
+    /// Whether cards are drawn with `Card::stylise_big`
+    pub big_cards: bool,
+    /// Screen position of each shown card, by index
+    pub cards: Vec<Rect>,
+    /// Number of columns between two columns of cards
+    pub gap: u16,
+    /// Row of the "cards not yet shown" line
+    pub pile: u16,
+    /// Row of the keybind table heading, if there is room for the table
+    pub keybinds: Option<u16>,
+    /// Row of the "Last input" line
+    pub last_input: u16,
+}
+
+impl Layout {
+    /// Lays out `card_count` cards and a keybind table of the given width and number of lines on a terminal of the given size.
+    /// The keybind table is left out if it does not fit.
+    /// Returns None if the terminal is too small to play on.
+    pub fn new(
+        width: u16,
+        height: u16,
+        card_count: usize,
+        (keybind_width, keybind_lines): (u16, u16),
+        card_size: CardSize,
+    ) -> Option<Self> {
+        let big_cards = match card_size {
+            CardSize::Auto => {
+                let (min_width, min_height) = CardSize::Big.min_size();
+                width >= min_width && height >= min_height
+            }
+            CardSize::Compact => false,
+            CardSize::Big => true,
+        };
+        let (min_width, min_height) = card_size.min_size();
+        // big cards have their own borders, so need only a single space between them
+        let (card_width, card_height, max_gap) = if big_cards {
+            (BIG_WIDTH, BIG_HEIGHT, 1)
+        } else {
+            (Theme::active().compact_width(), 1, MAX_GAP)
+        };
+        let columns = card_count.div_ceil(ROWS).max(1) as u16;
+        let spare_width = width.checked_sub(columns * card_width)?;
+        let gap = match columns {
+            1 => max_gap,
+            _ => (spare_width / (columns - 1)).min(max_gap),
+        };
+        if width < min_width || height < min_height || gap == 0 {
+            return None;
+        }
+        let cards = (0..card_count)
+            .map(|index| Rect {
+                x: (index / ROWS) as u16 * (card_width + gap),
+                y: 1 + (index % ROWS) as u16 * card_height,
+                width: card_width,
+                height: card_height,
+            })
+            .collect();
+        let pile = 1 + ROWS as u16 * card_height;
+        // heading, keybind lines, then the last input line
+        let keybinds =
+            (pile + 2 + keybind_lines < height && keybind_width <= width).then_some(pile + 1);
+        let last_input = match keybinds {
+            Some(row) => row + 1 + keybind_lines,
+            None => pile + 2,
+        };
+        Some(Self {
+            width,
+            height,
+            timer: (text("game.time_elapsed").chars().count() as u16, 0),
+            big_cards,
+            cards,
+            gap,
+            pile,
+            keybinds,
+            last_input,
+        })
+    }
+
+    /// Index of the card drawn at the given column and row, if any
+    pub fn card_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.cards
+            .iter()
+            .position(|rect| rect.contains(column, row))
+    }
+
+    /// What to print between two columns of cards
+    pub fn separator(&self) -> String {
+        if self.gap >= 3 && !self.big_cards {
+            let left = (self.gap - 1) / 2;
+            " ".repeat(left.into()) + "|" + &" ".repeat((self.gap - 1 - left).into())
+        } else {
+            " ".repeat(self.gap.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::{ENGLISH, FRENCH, LOCALES};
+
+    /// Checks that every cell of each card's rectangle resolves to that card, and that no other cell does
+    fn assert_cards_hit(layout: &Layout) {
+        let mut hits = 0;
+        for row in 0..layout.height {
+            for column in 0..layout.width {
+                let Some(index) = layout.card_at(column, row) else {
+                    continue;
+                };
+                assert!(layout.cards[index].contains(column, row));
+                hits += 1;
+            }
+        }
+        let area = layout.cards.iter().map(|rect| rect.width * rect.height);
+        assert_eq!(hits, area.sum::<u16>());
+    }
+
+    #[test]
+    fn clicks_on_compact_cards() {
+        let layout = Layout::new(40, 10, 12, (30, 4), CardSize::Compact).unwrap();
+        assert_eq!((layout.gap, layout.separator().as_str()), (5, "  |  "));
+        assert_cards_hit(&layout);
+        // the first card of each row, then the second column of cards after the separator
+        for (index, row) in (0..3).zip(1..) {
+            assert_eq!(layout.card_at(0, row), Some(index));
+            assert_eq!(layout.card_at(2, row), Some(index));
+            assert_eq!(layout.card_at(3, row), None);
+            assert_eq!(layout.card_at(5, row), None);
+            assert_eq!(layout.card_at(8, row), Some(index + 3));
+            // past the last column
+            assert_eq!(layout.card_at(24 + 3, row), None);
+            assert_eq!(layout.card_at(39, row), None);
+        }
+        assert_eq!(layout.card_at(0, 0), None);
+        assert_eq!(layout.card_at(0, layout.pile), None);
+    }
+
+    #[test]
+    fn clicks_on_big_cards() {
+        let layout = Layout::new(80, 30, 15, (30, 4), CardSize::Big).unwrap();
+        assert!(layout.big_cards);
+        assert_eq!(layout.gap, 1);
+        assert_cards_hit(&layout);
+        for (index, row) in (0..3).zip([1, 6, 11]) {
+            for line in row..row + BIG_HEIGHT {
+                assert_eq!(layout.card_at(0, line), Some(index));
+                assert_eq!(layout.card_at(BIG_WIDTH - 1, line), Some(index));
+                // the gap between the first and second column
+                assert_eq!(layout.card_at(BIG_WIDTH, line), None);
+                assert_eq!(layout.card_at(BIG_WIDTH + 1, line), Some(index + 3));
+                // past the fifth column
+                assert_eq!(layout.card_at(5 * (BIG_WIDTH + 1), line), None);
+            }
+        }
+        assert_eq!(layout.card_at(0, layout.pile), None);
+    }
+
+    #[test]
+    fn the_timer_line_fits_in_every_language() {
+        assert_eq!(min_width(&ENGLISH), 27);
+        assert_eq!(min_width(&FRENCH), 29);
+        for locale in LOCALES {
+            for key in ["game.time_elapsed", "game.time_left"] {
+                let width = locale.text(key).chars().count() as u16 + TIMER_WIDTH;
+                assert!(width <= min_width(locale), "{}: {key}", locale.code);
+            }
+        }
+    }
+}
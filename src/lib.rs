@@ -0,0 +1,21 @@
+pub mod accessible;
+pub mod animation;
+pub mod bitset;
+pub mod card;
+pub mod cli;
+pub mod config;
+pub mod drill;
+pub mod game;
+pub mod keymap;
+pub mod layout;
+pub mod locale;
+pub mod markdown;
+pub mod names;
+pub mod personal_best;
+pub mod puzzle;
+pub mod render;
+pub mod replay;
+pub mod summary;
+pub mod theme;
+pub mod tutorial;
+pub mod util;
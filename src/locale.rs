@@ -0,0 +1,438 @@
+use std::{fmt::Display, sync::OnceLock};
+
+use crate::names::{self, CardNames};
+
+static ACTIVE: OnceLock<&'static Locale> = OnceLock::new();
+
+/// A language the game can be played in: its message catalogue and the words used to describe cards
+pub struct Locale {
+    /// Language code accepted by `--lang` and matched against `LANG`, e.g. "fr"
+    pub code: &'static str,
+    /// Pairs of message keys and translated text. Text may contain placeholders such as {path}, filled in by `fill`.
+    pub messages: &'static [(&'static str, &'static str)],
+    pub names: &'static CardNames,
+    /// The rules and controls in markdown, also found in the README for English
+    pub tutorial: &'static str,
+}
+
+pub static ENGLISH: Locale = Locale {
+    code: "en",
+    messages: EN,
+    names: &names::ENGLISH,
+    tutorial: include_str!("../docs/tutorial.md"),
+};
+
+pub static FRENCH: Locale = Locale {
+    code: "fr",
+    messages: FR,
+    names: &names::FRENCH,
+    tutorial: include_str!("../docs/tutorial.fr.md"),
+};
+
+/// Every locale, English first
+pub static LOCALES: [&Locale; 2] = [&ENGLISH, &FRENCH];
+
+impl Locale {
+    /// Looks up a locale by language code, ignoring any region or encoding, e.g. "fr_FR.UTF-8" finds French
+    pub fn find(code: &str) -> Option<&'static Self> {
+        let language = code.split(['_', '-', '.', '@']).next()?.to_lowercase();
+        LOCALES
+            .iter()
+            .copied()
+            .find(|locale| locale.code == language)
+    }
+
+    /// The locale named by the first set of `LC_ALL`, `LC_MESSAGES` and `LANG`, if it is supported
+    pub fn from_env() -> Option<&'static Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::find(&value))
+    }
+
+    /// The locale used for all text. Defaults to English if none was set.
+    pub fn active() -> &'static Self {
+        ACTIVE.get_or_init(|| &ENGLISH)
+    }
+
+    /// Sets the locale used for all text. Has no effect once any text has been shown.
+    pub fn set_active(locale: &'static Self) {
+        let _ = ACTIVE.set(locale);
+    }
+
+    /// The text for a message key, falling back to English, then to the key itself
+    pub fn text(&self, key: &'static str) -> &'static str {
+        let lookup = |messages: &'static [(&str, &'static str)]| {
+            messages.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+        };
+        lookup(self.messages)
+            .or_else(|| lookup(ENGLISH.messages))
+            .unwrap_or(key)
+    }
+}
+
+/// The text for a message key in the active locale
+pub fn text(key: &'static str) -> &'static str {
+    Locale::active().text(key)
+}
+
+/// The text for a message key in the active locale, with each {name} placeholder replaced by its value
+pub fn fill(key: &'static str, values: &[(&str, &dyn Display)]) -> String {
+    substitute(text(key), values)
+}
+
+/// Replaces each {name} placeholder in text by its value
+pub fn substitute(text: &str, values: &[(&str, &dyn Display)]) -> String {
+    values.iter().fold(text.to_owned(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), &value.to_string())
+    })
+}
+
+static EN: &[(&str, &str)] = &[
+    ("menu.title", "rust-set v{version} - Rust implementation of Set"),
+    ("menu.prompt", "Press h for tutorial, s to start, p for a puzzle, t for time attack, d for the third-card drill or Q to quit (case sensitive)"),
+    ("menu.quit", "Q pressed, exiting..."),
+    ("config.problems", "Problems in config file {path}:"),
+    ("config.ignored", "These options were ignored."),
+    ("bests.unreadable", "Could not read personal bests from {path}: {error}"),
+    ("bests.out_of_order", "the split times of a run must increase"),
+    ("bests.not_saved", "New personal bests will not be saved until the file is fixed or removed."),
+    ("game.too_small", "Terminal too small, please resize to at least {width}x{height}"),
+    ("game.time_elapsed", "Time elapsed: "),
+    ("game.time_left", "Time left: "),
+    ("game.keybind_table", "Keybind table"),
+    ("game.call_no_set_key", "N to call \"no set\""),
+    ("game.hint_key", "? for a hint"),
+    ("game.split", "Set {set}: {lap}"),
+    ("game.last_input", "Last input: "),
+    ("game.completed", "Set completed in "),
+    ("game.pile", "cards not yet shown"),
+    ("game.score", "score: "),
+    ("keys.cursor", "Arrows/HJKL move cursor, Space/Enter toggle"),
+    ("keys.quit", "Q to return to the menu"),
+    ("input.nothing", "<nothing>"),
+    ("input.click", "<mouse click>"),
+    ("input.right_click", "<right click>"),
+    ("status.selected", "Selected"),
+    ("status.deselected", "Deselected"),
+    ("status.form_set", "form a set!"),
+    ("status.not_set", "do not form a set!"),
+    ("status.broken", "Not all the same or all different: {attributes}"),
+    ("status.out_of_bounds", "Selection out of bounds"),
+    ("status.nothing_selected", "Nothing selected"),
+    ("status.not_selected", "Card was not selected"),
+    ("status.no_binding", "Key has no known binding"),
+    ("status.move_cursor", "Use the arrow keys to move the cursor first"),
+    ("status.correct_no_set", "Correct, there was no set! (+1)"),
+    ("status.wrong_no_set", "Wrong, there was a set (highlighted)! (-1)"),
+    ("status.hint", "The highlighted card is part of a set"),
+    ("status.no_hint", "There is no set on the table"),
+    ("status.hints_off", "There are no hints when calling \"no set\""),
+    ("tutorial.intro", "Set! is a concentration game invented by Marsha Falco in 1974. `rust-set` is a single-player implementation of Set written in Rust."),
+    ("tutorial.continue", "Press any key to continue, or Q to return to the menu."),
+    ("tutorial.shape", "Every card shows pills, diamonds or squiggles. These cards differ only in their shape:"),
+    ("tutorial.number", "Every card shows one, two or three shapes. These cards differ only in their number:"),
+    ("tutorial.colour", "Every card is {first}, {second} or {third}. These cards differ only in their colour:"),
+    ("tutorial.decoration", "Every card is plain, underlined or double-underlined. These cards differ only in their decoration:"),
+    ("tutorial.rule", "In a set, the {attribute} of the three cards must be all the same or all different. Try these:"),
+    ("tutorial.answer", "Is this a set? Press y or n."),
+    ("tutorial.correct", "Correct!"),
+    ("tutorial.incorrect", "Not quite."),
+    ("tutorial.same", "all the same"),
+    ("tutorial.different", "all different"),
+    ("tutorial.mixed", "two the same and one different, so this is not a set"),
+    ("tutorial.is_set", "Every attribute is all the same or all different, so this is a set."),
+    ("tutorial.everything", "Now all four attributes can change at once. Check each of them in turn."),
+    ("tutorial.table", "Finally, find a set on this table by pressing the keys next to three cards."),
+    ("tutorial.hint", "Hint: {card} is part of a set."),
+    ("tutorial.found", "Well done, you found a set! You are ready to play."),
+    ("tutorial.summary", "Here is a summary of the rules and controls:"),
+    ("tutorial.have_fun", "Have fun!"),
+    ("time_attack.over", "Time's up! You found {sets} sets in {time}."),
+    ("summary.game_over", "Game over!"),
+    ("summary.time_up", "Time's up!"),
+    ("summary.time", "Total time: {time}"),
+    ("summary.sets", "Sets found: {sets}"),
+    ("summary.mistakes", "Mistakes: {mistakes}"),
+    ("summary.hints", "Hints: {hints}"),
+    ("summary.splits", "Split times"),
+    ("summary.chart", "Time per set"),
+    ("summary.best", "Personal best: {time}"),
+    ("summary.new_best", "New personal best!"),
+    ("summary.best_not_saved", "Could not save the personal best: {error}"),
+    ("summary.options", "n to play again, r to replay the same cards, s to save a replay, m for the menu, Q to quit"),
+    ("summary.saved", "Replay saved to {path}"),
+    ("summary.not_saved", "Could not save the replay: {error}"),
+    ("puzzle.progress", "Sets found: {found} of {sets}"),
+    ("puzzle.found", "Found a new set!"),
+    ("puzzle.duplicate", "You have already found that set"),
+    ("puzzle.solved", "All {sets} sets found in {time}! Press any key to return to the menu."),
+    ("drill.intro", "Pick the card which forms a set with the two cards shown by pressing its number. Press Q to stop and see which attributes slow you down."),
+    ("drill.correct", "Correct! ({time})"),
+    ("drill.wrong", "Not quite: it was {number}, {card}. Your choice had a different {attribute}."),
+    ("drill.stats", "Results by attribute"),
+    ("drill.attribute", "Attribute"),
+    ("drill.accuracy", "Correct"),
+    ("drill.time", "Average time"),
+    ("drill.mistakes", "Mistakes"),
+    ("drill.slowest", "You take longest when the {attribute} is what matters."),
+    ("drill.no_questions", "No questions answered."),
+    ("drill.return", "Press any key to return to the menu."),
+    ("accessible.intro", "rust-set accessible mode. Find sets of three cards where each attribute is all the same or all different."),
+    ("accessible.help", "Commands:\n  table (or t): list the cards on the table\n  select A B C (or s A B C): check whether cards A, B and C form a set, e.g. select 3 7 11\n  no set (or n): claim there is no set on the table, if playing with --call-no-set\n  help (or h): show this message\n  quit (or q): stop playing"),
+    ("accessible.stopped", "Stopped playing."),
+    ("accessible.no_call_no_set", "Calling no set is only possible when playing with --call-no-set."),
+    ("accessible.correct_no_set", "Correct, there was no set. You gain a point."),
+    ("accessible.wrong_no_set", "Wrong, cards {a}, {b} and {c} form a set. You lose a point."),
+    ("accessible.bad_selection", "Please give three different card numbers from 1 to {count}, for example: select 1 2 3"),
+    ("accessible.set_found", "Set found: {a}, {b} and {c}."),
+    ("accessible.not_set", "Not a set: {a}, {b} and {c}."),
+    ("accessible.broken", "Not all the same or all different: {attributes}."),
+    ("accessible.unknown", "Unknown command. Type help for a list of commands."),
+    ("accessible.game_over", "Game over! You found {sets} sets in {time}."),
+    ("accessible.score", "Your score is {score}."),
+    ("accessible.table", "{shown} cards on the table, {pile} cards not yet shown:"),
+    ("duration.and", "{minutes} and {seconds}"),
+    ("duration.minute", "{n} minute"),
+    ("duration.minutes", "{n} minutes"),
+    ("duration.second", "{n} second"),
+    ("duration.seconds", "{n} seconds"),
+    ("usage", "Usage: rust-set [OPTIONS]
+
+Options:
+  --preset <standard|speed|relaxed>  Start from a preset table (12, 9 or 15 cards)
+  --table-size <N>                   Number of cards dealt onto the table (multiple of 3, at most 21)
+  --extra-draw <N>                   Number of cards added at a time when there is no set (multiple of 3)
+  --refill-extras                    Replace extra cards after a set is found instead of shrinking the table
+  --call-no-set                      Tournament mode: press N to call \"no set\" instead of extra cards being dealt automatically
+  --puzzle-sets <N>                  Number of sets hidden on the 12 cards of a puzzle (1 to 14, default 6)
+  --time-limit <SECONDS>             Length of a time-attack game (default 180)
+  --cards <auto|big|compact>         Draw boxed cards, 3-character cards, or big cards only if they fit (default)
+  --theme <NAME>                     Card colours and glyphs: standard, colour-blind, glyph (colour letters) or ascii (no colour or underlines)
+  --keys <LAYOUT>                    Keys in the same positions as q-u, a-j and z-m on QWERTY for: qwerty, qwertz, azerty, dvorak or colemak
+  --lang <en|fr>                     Language of the game, instead of the one set by LANG
+  --no-animations                    Don't flash found and invalid sets or highlight extra cards
+  --accessible                       Play with typed commands and cards described in words, without colours or cursor movement, for screen readers
+  --config <PATH>                    Read settings from PATH instead of rust-set/config.toml in the user's config directory
+  --print-config                     Print an example config file with every setting and exit
+  --help                             Print this message"),
+    ("error.panic", "An error occurred: {error}"),
+    ("args.requires_value", "{arg} requires a value"),
+    ("args.not_a_number", "{arg} expects a number, got {value}"),
+    ("args.unknown", "unknown argument {arg}"),
+    ("args.unknown_preset", "unknown preset {preset}"),
+    ("args.unknown_cards", "unknown card size {cards}"),
+    ("args.unknown_lang", "unknown language {code}, expected one of {expected}"),
+    ("args.puzzle_sets", "--puzzle-sets must be from 1 to {max}"),
+    ("args.time_limit", "--time-limit must be at least 1 second"),
+    ("error.unknown_theme", "unknown theme {name}, expected one of {expected}"),
+    ("error.unknown_keys", "unknown keyboard layout {name}, expected one of {expected}"),
+    ("error.table_size", "table size must be a multiple of 3 between 3 and {max}, got {size}"),
+    ("error.extra_draw", "extra draw size must be a positive multiple of 3, got {extra}"),
+    ("error.table_overflow", "drawing {extra} extra cards at a time onto a {size}-card table could exceed {max} cards"),
+    ("config.line", "line {line}: {error}"),
+    ("config.count", "{name} needs 3 values, got {count}"),
+    ("config.glyph", "{value} is not a single visible character one column wide"),
+    ("config.colour", "{value} is not a colour name or #rrggbb hex code"),
+    ("config.decoration", "{value} is not a known decoration"),
+    ("keys.row_length", "row {row} of the key grid has {count} keys, expected {expected}"),
+    ("keys.unusable", "{key} cannot be used to select cards"),
+    ("keys.duplicate", "{key} appears more than once in the key grid"),
+];
+
+static FR: &[(&str, &str)] = &[
+    ("menu.title", "rust-set v{version} - implémentation en Rust de Set"),
+    ("menu.prompt", "Appuyez sur h pour le tutoriel, s pour commencer, p pour une énigme, t pour un contre-la-montre, d pour l'entraînement à la troisième carte ou Q pour quitter (sensible à la casse)"),
+    ("menu.quit", "Q appuyé, fermeture..."),
+    ("config.problems", "Problèmes dans le fichier de configuration {path} :"),
+    ("config.ignored", "Ces options ont été ignorées."),
+    ("bests.unreadable", "Impossible de lire les records personnels de {path} : {error}"),
+    ("bests.out_of_order", "les temps intermédiaires d'une partie doivent être croissants"),
+    ("bests.not_saved", "Les nouveaux records personnels ne seront pas enregistrés tant que le fichier n'est pas corrigé ou supprimé."),
+    ("game.too_small", "Terminal trop petit, veuillez l'agrandir à au moins {width}x{height}"),
+    ("game.time_elapsed", "Temps écoulé : "),
+    ("game.time_left", "Temps restant : "),
+    ("game.keybind_table", "Touches"),
+    ("game.call_no_set_key", "N pour annoncer « pas de set »"),
+    ("game.hint_key", "? pour un indice"),
+    ("game.split", "Set {set} : {lap}"),
+    ("game.last_input", "Dernière saisie : "),
+    ("game.completed", "Partie terminée en "),
+    ("game.pile", "cartes pas encore montrées"),
+    ("game.score", "score : "),
+    ("keys.cursor", "Flèches/HJKL déplacent le curseur, Espace/Entrée sélectionne"),
+    ("keys.quit", "Q pour revenir au menu"),
+    ("input.nothing", "<rien>"),
+    ("input.click", "<clic>"),
+    ("input.right_click", "<clic droit>"),
+    ("status.selected", "Sélectionné :"),
+    ("status.deselected", "Désélectionné :"),
+    ("status.form_set", "forment un set !"),
+    ("status.not_set", "ne forment pas un set !"),
+    ("status.broken", "Ni identiques ni tous différents : {attributes}"),
+    ("status.out_of_bounds", "Sélection hors du tableau"),
+    ("status.nothing_selected", "Rien n'est sélectionné"),
+    ("status.not_selected", "La carte n'était pas sélectionnée"),
+    ("status.no_binding", "Cette touche n'a pas d'effet"),
+    ("status.move_cursor", "Déplacez d'abord le curseur avec les flèches"),
+    ("status.correct_no_set", "Exact, il n'y avait pas de set ! (+1)"),
+    ("status.wrong_no_set", "Faux, il y avait un set (en surbrillance) ! (-1)"),
+    ("status.hint", "La carte en surbrillance fait partie d'un set"),
+    ("status.no_hint", "Il n'y a pas de set sur la table"),
+    ("status.hints_off", "Pas d'indice quand on annonce « pas de set »"),
+    ("tutorial.intro", "Set! est un jeu de réflexion inventé par Marsha Falco en 1974. `rust-set` est une version solo de Set écrite en Rust."),
+    ("tutorial.continue", "Appuyez sur une touche pour continuer, ou sur Q pour revenir au menu."),
+    ("tutorial.shape", "Chaque carte montre des ovales, des losanges ou des serpentins. Ces cartes ne diffèrent que par leur forme :"),
+    ("tutorial.number", "Chaque carte montre une, deux ou trois formes. Ces cartes ne diffèrent que par leur nombre :"),
+    ("tutorial.colour", "Chaque carte est imprimée en {first}, {second} ou {third}. Ces cartes ne diffèrent que par leur couleur :"),
+    ("tutorial.decoration", "Chaque carte est simple, soulignée ou doublement soulignée. Ces cartes ne diffèrent que par leur décoration :"),
+    ("tutorial.rule", "Dans un set, l'attribut {attribute} des trois cartes doit être identique partout ou différent partout. Essayez :"),
+    ("tutorial.answer", "Est-ce un set ? Appuyez sur y (oui) ou n (non)."),
+    ("tutorial.correct", "Exact !"),
+    ("tutorial.incorrect", "Pas tout à fait."),
+    ("tutorial.same", "identique partout"),
+    ("tutorial.different", "différent partout"),
+    ("tutorial.mixed", "deux identiques et un différent, ce n'est donc pas un set"),
+    ("tutorial.is_set", "Chaque attribut est identique partout ou différent partout : c'est un set."),
+    ("tutorial.everything", "Maintenant, les quatre attributs peuvent changer à la fois. Vérifiez-les un par un."),
+    ("tutorial.table", "Pour finir, trouvez un set sur cette table en appuyant sur les touches à côté de trois cartes."),
+    ("tutorial.hint", "Indice : {card} fait partie d'un set."),
+    ("tutorial.found", "Bravo, vous avez trouvé un set ! Vous êtes prêt à jouer."),
+    ("tutorial.summary", "Voici un résumé des règles et des commandes :"),
+    ("tutorial.have_fun", "Amusez-vous bien !"),
+    ("time_attack.over", "Temps écoulé ! Vous avez trouvé {sets} sets en {time}."),
+    ("summary.game_over", "Partie terminée !"),
+    ("summary.time_up", "Temps écoulé !"),
+    ("summary.time", "Temps total : {time}"),
+    ("summary.sets", "Sets trouvés : {sets}"),
+    ("summary.mistakes", "Erreurs : {mistakes}"),
+    ("summary.hints", "Indices : {hints}"),
+    ("summary.splits", "Temps intermédiaires"),
+    ("summary.chart", "Temps par set"),
+    ("summary.best", "Record personnel : {time}"),
+    ("summary.new_best", "Nouveau record personnel !"),
+    ("summary.best_not_saved", "Impossible d'enregistrer le record personnel : {error}"),
+    ("summary.options", "n pour rejouer, r pour rejouer les mêmes cartes, s pour enregistrer la partie, m pour le menu, Q pour quitter"),
+    ("summary.saved", "Partie enregistrée dans {path}"),
+    ("summary.not_saved", "Impossible d'enregistrer la partie : {error}"),
+    ("puzzle.progress", "Sets trouvés : {found} sur {sets}"),
+    ("puzzle.found", "Nouveau set trouvé !"),
+    ("puzzle.duplicate", "Vous avez déjà trouvé ce set"),
+    ("puzzle.solved", "Les {sets} sets trouvés en {time} ! Appuyez sur une touche pour revenir au menu."),
+    ("drill.intro", "Choisissez la carte qui forme un set avec les deux cartes montrées en appuyant sur son numéro. Appuyez sur Q pour arrêter et voir quels attributs vous ralentissent."),
+    ("drill.correct", "Exact ! ({time})"),
+    ("drill.wrong", "Pas tout à fait : c'était {number}, {card}. Votre choix avait un attribut {attribute} différent."),
+    ("drill.stats", "Résultats par attribut"),
+    ("drill.attribute", "Attribut"),
+    ("drill.accuracy", "Réussis"),
+    ("drill.time", "Temps moyen"),
+    ("drill.mistakes", "Erreurs"),
+    ("drill.slowest", "Vous êtes le plus lent quand l'attribut {attribute} est décisif."),
+    ("drill.no_questions", "Aucune question répondue."),
+    ("drill.return", "Appuyez sur une touche pour revenir au menu."),
+    ("accessible.intro", "rust-set, mode accessible. Trouvez des sets de trois cartes dont chaque attribut est soit identique, soit différent sur les trois."),
+    ("accessible.help", "Commandes :\n  table (ou t) : lister les cartes sur la table\n  select A B C (ou s A B C) : vérifier si les cartes A, B et C forment un set, par ex. select 3 7 11\n  no set (ou n) : annoncer qu'il n'y a pas de set sur la table, en jouant avec --call-no-set\n  help (ou h) : afficher ce message\n  quit (ou q) : arrêter de jouer"),
+    ("accessible.stopped", "Partie arrêtée."),
+    ("accessible.no_call_no_set", "Annoncer « pas de set » n'est possible qu'en jouant avec --call-no-set."),
+    ("accessible.correct_no_set", "Exact, il n'y avait pas de set. Vous gagnez un point."),
+    ("accessible.wrong_no_set", "Faux, les cartes {a}, {b} et {c} forment un set. Vous perdez un point."),
+    ("accessible.bad_selection", "Donnez trois numéros de carte différents entre 1 et {count}, par exemple : select 1 2 3"),
+    ("accessible.set_found", "Set trouvé : {a}, {b} et {c}."),
+    ("accessible.not_set", "Pas un set : {a}, {b} et {c}."),
+    ("accessible.broken", "Ni identiques ni tous différents : {attributes}."),
+    ("accessible.unknown", "Commande inconnue. Tapez help pour la liste des commandes."),
+    ("accessible.game_over", "Partie terminée ! Vous avez trouvé {sets} sets en {time}."),
+    ("accessible.score", "Votre score est de {score}."),
+    ("accessible.table", "{shown} cartes sur la table, {pile} cartes pas encore montrées :"),
+    ("duration.and", "{minutes} et {seconds}"),
+    ("duration.minute", "{n} minute"),
+    ("duration.minutes", "{n} minutes"),
+    ("duration.second", "{n} seconde"),
+    ("duration.seconds", "{n} secondes"),
+    ("usage", "Utilisation : rust-set [OPTIONS]
+
+Options :
+  --preset <standard|speed|relaxed>  Partir d'une table prédéfinie (12, 9 ou 15 cartes)
+  --table-size <N>                   Nombre de cartes posées sur la table (multiple de 3, 21 au plus)
+  --extra-draw <N>                   Nombre de cartes ajoutées à la fois quand il n'y a pas de set (multiple de 3)
+  --refill-extras                    Remplacer les cartes supplémentaires après un set au lieu de réduire la table
+  --call-no-set                      Mode tournoi : appuyer sur N pour annoncer « pas de set » au lieu de recevoir automatiquement des cartes supplémentaires
+  --puzzle-sets <N>                  Nombre de sets cachés parmi les 12 cartes d'une énigme (1 à 14, 6 par défaut)
+  --time-limit <SECONDES>            Durée d'une partie contre la montre (180 par défaut)
+  --cards <auto|big|compact>         Dessiner des cartes encadrées, des cartes de 3 caractères, ou de grandes cartes seulement si elles tiennent (par défaut)
+  --theme <NOM>                      Couleurs et symboles des cartes : standard, colour-blind, glyph (lettres de couleur) ou ascii (sans couleurs ni soulignement)
+  --keys <DISPOSITION>               Touches aux mêmes positions que q-u, a-j et z-m en QWERTY pour : qwerty, qwertz, azerty, dvorak ou colemak
+  --lang <en|fr>                     Langue du jeu, au lieu de celle donnée par LANG
+  --no-animations                    Ne pas faire clignoter les sets trouvés et invalides ni surligner les cartes supplémentaires
+  --accessible                       Jouer avec des commandes tapées et des cartes décrites en mots, sans couleurs ni déplacements du curseur, pour les lecteurs d'écran
+  --config <CHEMIN>                  Lire les réglages depuis CHEMIN au lieu de rust-set/config.toml dans le dossier de configuration de l'utilisateur
+  --print-config                     Afficher un exemple de fichier de configuration avec chaque réglage et quitter
+  --help                             Afficher ce message"),
+    ("error.panic", "Une erreur s'est produite : {error}"),
+    ("args.requires_value", "{arg} attend une valeur"),
+    ("args.not_a_number", "{arg} attend un nombre, reçu {value}"),
+    ("args.unknown", "argument inconnu {arg}"),
+    ("args.unknown_preset", "table prédéfinie inconnue {preset}"),
+    ("args.unknown_cards", "taille de carte inconnue {cards}"),
+    ("args.unknown_lang", "langue inconnue {code}, attendu l'une de {expected}"),
+    ("args.puzzle_sets", "--puzzle-sets doit être entre 1 et {max}"),
+    ("args.time_limit", "--time-limit doit être d'au moins 1 seconde"),
+    ("error.unknown_theme", "thème inconnu {name}, attendu l'un de {expected}"),
+    ("error.unknown_keys", "disposition de clavier inconnue {name}, attendu l'une de {expected}"),
+    ("error.table_size", "la taille de la table doit être un multiple de 3 entre 3 et {max}, reçu {size}"),
+    ("error.extra_draw", "le nombre de cartes supplémentaires doit être un multiple de 3 positif, reçu {extra}"),
+    ("error.table_overflow", "ajouter {extra} cartes à la fois à une table de {size} cartes pourrait dépasser {max} cartes"),
+    ("config.line", "ligne {line} : {error}"),
+    ("config.count", "{name} attend 3 valeurs, reçu {count}"),
+    ("config.glyph", "{value} n'est pas un unique caractère visible d'une colonne de large"),
+    ("config.colour", "{value} n'est ni un nom de couleur ni un code hexadécimal #rrggbb"),
+    ("config.decoration", "{value} n'est pas une décoration connue"),
+    ("keys.row_length", "la ligne {row} de la grille de touches a {count} touches, {expected} attendues"),
+    ("keys.unusable", "{key} ne peut pas servir à sélectionner des cartes"),
+    ("keys.duplicate", "{key} apparaît plusieurs fois dans la grille de touches"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn every_key_exists_in_every_catalogue() {
+        for locale in LOCALES {
+            for (i, (key, _)) in locale.messages.iter().enumerate() {
+                assert!(
+                    !locale.messages[..i].iter().any(|(k, _)| k == key),
+                    "{key} appears twice in {}",
+                    locale.code
+                );
+                assert!(
+                    ENGLISH.messages.iter().any(|(k, _)| k == key),
+                    "{key} in {} is not an English key",
+                    locale.code
+                );
+            }
+            for (key, english) in ENGLISH.messages {
+                let Some((_, text)) = locale.messages.iter().find(|(k, _)| k == key) else {
+                    panic!("{key} is missing from {}", locale.code);
+                };
+                assert_eq!(
+                    placeholders(english),
+                    placeholders(text),
+                    "{key} in {} has different placeholders",
+                    locale.code
+                );
+            }
+        }
+    }
+}
@@ -1,39 +1,85 @@
-mod card;
-use std::{
-    io::Write,
-    sync::{Arc, Mutex},
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Print as P, PrintStyledContent as PS, Stylize},
+    terminal::{Clear, ClearType},
 };
-mod util;
-use crossterm::execute;
-use rand::seq::SliceRandom;
-use util::*;
-mod tutorial;
-use tutorial::*;
-mod game;
-use game::Game;
-
+use rust_set::animation::Animations;
+use rust_set::cli::{language, Options};
+use rust_set::config::{Config, EXAMPLE};
+use rust_set::game::{Game, GameConfig, NoSetCall};
+use rust_set::keymap::Keymap;
+use rust_set::layout::{neighbour, CardSize, Direction, Layout, TIMER_WIDTH};
+use rust_set::locale::{fill, text, Locale};
+use rust_set::personal_best::{compare, laps, PersonalBests};
+use rust_set::render::Frame;
+use rust_set::replay::Replay;
+use rust_set::summary::{self, Action, Choice};
+use rust_set::theme::Theme;
+use rust_set::tutorial::*;
+use rust_set::util::*;
+use rust_set::{accessible, drill, puzzle};
+use std::{io::Write, time::Duration};
 
 // suspicious path thing
 #[cfg(windows)]
-static LICENCE:&str = std::include_str!("..\\LICENCE");
+static LICENCE: &str = std::include_str!("..\\LICENCE");
 #[cfg(unix)]
-static LICENCE:&str = std::include_str!("../LICENCE");
+static LICENCE: &str = std::include_str!("../LICENCE");
 
 static S: crossterm::style::Print<&str> = crossterm::style::Print(" ");
 fn main() {
     std::panic::set_hook(Box::new(|panic_info| {
-        exit(|| println!("An error occured: {panic_info}"), 1)
+        exit(
+            || println!("{}", fill("error.panic", &[("error", panic_info)])),
+            1,
+        )
     }));
     main_fn().unwrap();
 }
 
 // ensure question mark syntax panics so panic handler handles it
 fn main_fn() -> Result<(), Box<dyn std::error::Error>> {
-    use crossterm::{
-        cursor::*, event::*, queue, style::Print as P, style::PrintStyledContent as PS,
-        style::Stylize, terminal::*,
+    use crossterm::{cursor::*, event::*, terminal::*};
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    // set first so that problems with the arguments and config file are reported in the chosen language
+    Locale::set_active(language(&args));
+    let options = match Options::parse(args) {
+        Ok(None) => {
+            println!("{}", text("usage"));
+            return Ok(());
+        }
+        Ok(Some(Options {
+            print_config: true, ..
+        })) => {
+            print!("{EXAMPLE}");
+            return Ok(());
+        }
+        Ok(Some(options)) => options,
+        Err(message) => {
+            eprintln!("{message}\n\n{}", text("usage"));
+            std::process::exit(2);
+        }
     };
+    let config_path = options.config.clone().or_else(Config::default_path);
+    let (config, config_errors) = match &config_path {
+        Some(path) => Config::load(path, options.theme.clone(), options.keymap.clone()),
+        None => (
+            Config {
+                theme: options.theme.clone().unwrap_or_default(),
+                keymap: options.keymap.clone().unwrap_or_default(),
+            },
+            Vec::new(),
+        ),
+    };
+    Theme::set_active(config.theme);
+    Keymap::set_active(config.keymap);
     let mut stdout = std::io::stdout();
+    if options.accessible {
+        let game = Game::from_seed(rand::random(), options.game);
+        accessible::play(game, std::io::stdin().lock(), &mut stdout)?;
+        return Ok(());
+    }
 
     queue!(
         stdout,
@@ -42,155 +88,551 @@ fn main_fn() -> Result<(), Box<dyn std::error::Error>> {
         Hide,
         SavePosition,
         MoveTo(0, 0),
-        P("rust-set v0.0.2 - Rust implementation of Set\n"),
+        P(fill(
+            "menu.title",
+            &[("version", &env!("CARGO_PKG_VERSION"))]
+        )),
+        P("\n"),
         P(LICENCE),
         P("\n\n")
     )?;
-    enable_raw_mode()?;
-    loop {
+    if let (false, Some(path)) = (config_errors.is_empty(), &config_path) {
+        queue!(
+            stdout,
+            PS(fill("config.problems", &[("path", &path.display())]).yellow()),
+            P("\n")
+        )?;
+        for error in &config_errors {
+            queue!(stdout, P(format!("- {error}\n")))?;
+        }
+        queue!(stdout, P(text("config.ignored")), P("\n\n"))?;
+    }
+    let (mut bests, bests_error) = PersonalBests::load(config_path.as_deref());
+    if let Some(error) = bests_error {
         queue!(
             stdout,
-            P("Press h for tutorial, s to start or Q to quit (case sensitive)"),
-            MoveToNextLine(1)
+            PS(error.yellow()),
+            P("\n"),
+            P(text("bests.not_saved")),
+            P("\n\n")
         )?;
+    }
+    enable_raw_mode()?;
+    let remarks = loop {
+        queue!(stdout, P(text("menu.prompt")), MoveToNextLine(1))?;
         stdout.flush()?;
         let ev = read_char(false)?;
-        if let (KeyCode::Char(char), ..) = ev {
-            if char == 'h' {
-                tutorial()?;
-            } else if char == 's' {
-                break;
-            } else if char == 'Q' {
-                exit(|| println!("Q pressed, exiting..."), 0)
+        if let Input::Key(KeyCode::Char(char)) = ev {
+            let time_limit = match char {
+                's' => None,
+                't' => Some(options.time_limit),
+                'Q' => break text("menu.quit").to_owned(),
+                _ => {
+                    play_mode(&mut stdout, char, &options)?;
+                    continue;
+                }
+            };
+            let config = GameConfig {
+                time_limit,
+                ..options.game
+            };
+            if let Some(remarks) = play_games(&mut stdout, config, &options, &mut bests)? {
+                break remarks;
             }
+            queue!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
         }
-    }
+    };
+    restore_terminal();
+    println!("{remarks}");
+    Ok(())
+}
 
-    let mut card_ids = (0u8..81).collect::<Vec<_>>();
-    card_ids.shuffle(&mut rand::thread_rng());
-    let state_mutex = Arc::new(Mutex::new(Game::new(card_ids.into_iter())));
-    let clone_state_mutex = Arc::clone(&state_mutex);
-    // increment timer
-    queue!(
-        stdout,
-        EnableMouseCapture,
-        Clear(ClearType::All),
-        MoveTo(0, 0),
-        PS("Time elapsed: ".bold()),
-    )?;
-    let game = state_mutex.lock().unwrap();
-    game.print(&mut stdout)?;
-    std::mem::drop(game);
-    queue!(
-        stdout,
-        MoveTo(0, 5),
-        P("Keybind table".bold()),
-        MoveToNextLine(1),
-        P(KEYBINDS),
-        MoveToNextLine(1),
-        PS("Last input: ".bold()),
-        P("<nothing>")
-    )?;
-    std::thread::spawn(|| {
-        let mut stdout = std::io::stdout();
-        let started = std::time::Instant::now();
-        let state_mutex = clone_state_mutex;
-        loop {
-            std::thread::yield_now();
-            let game = state_mutex.lock().unwrap();
-            let now = game.ended.unwrap_or_else(|| std::time::Instant::now());
-            let elapsed = (now - started).as_millis();
-            let elapsed_millis = elapsed % 1000;
-            let elapsed_secs = (elapsed % 60000 - elapsed_millis) / 1000;
-            let elapsed_mins = (elapsed % 3600000 - elapsed_millis - elapsed_secs * 1000) / 60000;
-            let elapsed_hs =
-                (elapsed - elapsed_millis - elapsed_secs * 1000 - elapsed_mins * 60000) / 3600000;
-            if game.ended.is_some() {
-                exit(
-                    || {
-                        execute!(stdout,
-                    P("Set completed in "),
-                    PS(format!("{elapsed_hs:02}:{elapsed_mins:02}:{elapsed_secs:02}.{elapsed_millis:03}s").bold())
-                ).unwrap()
-                    },
-                    0,
-                )
-            } else {
-                queue!(
-                    stdout,
-                    MoveTo(14, 0),
-                    P(format!(
-                        "{elapsed_hs:02}:{elapsed_mins:02}:{elapsed_secs:02}.{elapsed_millis:03}s"
-                    ))
-                )
-                .unwrap();
-            }
-            stdout.flush().unwrap();
-            std::mem::drop(game);
-        }
-    });
-    loop {
-        let read = read_char(true)?;
-        let mut game = state_mutex.lock().unwrap();
+/// Runs the mode chosen on the main menu with the given key, other than playing games
+fn play_mode(stdout: &mut std::io::Stdout, key: char, options: &Options) -> std::io::Result<()> {
+    use crossterm::event::*;
+    if key == 'h' {
+        tutorial()?;
+    } else if key == 'd' {
+        drill::play(stdout)?;
+    } else if key == 'p' {
+        queue!(stdout, EnableMouseCapture)?;
+        puzzle::play(stdout, options.puzzle_sets, options.card_size)?;
         queue!(
             stdout,
-            MoveTo(0, 1),
-            MoveTo(12, 10),
-            Clear(ClearType::UntilNewLine),
-            P(if read.1 {
-                read.0.to_string()
-            } else {
-                "<mouse click>".to_string()
-            }),
-            P(" => ")
+            DisableMouseCapture,
+            Clear(ClearType::All),
+            MoveTo(0, 0)
         )?;
-        if let (crossterm::event::KeyCode::Char(char), ..) = read {
-            if char == 'Q' {
-                exit(|| println!("Requested exit (Q pressed), exiting..."), 0);
+    }
+    Ok(())
+}
+
+/// Plays games one after another until the player returns to the menu, giving None,
+/// or quits, giving what to print once the terminal is restored
+fn play_games(
+    stdout: &mut std::io::Stdout,
+    config: GameConfig,
+    options: &Options,
+    bests: &mut PersonalBests,
+) -> std::io::Result<Option<String>> {
+    let mut seed = rand::random();
+    loop {
+        let (choice, game) = play(stdout, Game::from_seed(seed, config), options, bests)?;
+        match choice {
+            Choice::NewDeal => seed = rand::random(),
+            Choice::SameDeal => {}
+            Choice::Menu => return Ok(None),
+            Choice::Quit => return Ok(Some(final_remarks(&game))),
+        }
+    }
+}
+
+/// Plays a game, then shows the summary until the player chooses what to do next.
+/// Returns that choice, or `Choice::Menu` if Q was pressed during the game, with the finished game.
+/// Splits are compared with the personal best for the table setup, which is updated if the game beats it.
+fn play(
+    stdout: &mut std::io::Stdout,
+    game: Game,
+    options: &Options,
+    bests: &mut PersonalBests,
+) -> std::io::Result<(Choice, Game)> {
+    use crossterm::{event::*, terminal::size};
+    let (keybind_width, keybind_lines) = Keymap::active().keybinds_size(true);
+    let extra_keys = extra_keys(game.config());
+    let keybind_size = (
+        keybind_width.max(extra_keys.chars().count() as u16 + 2),
+        keybind_lines + 1,
+    );
+    let mut screen = Screen {
+        best: bests.splits(game.config()),
+        game,
+        layout: None,
+        size: size()?,
+        card_size: options.card_size,
+        keybind_size,
+        last_input: Vec::new(),
+        cursor: None,
+        summary: None,
+        animations: Animations::new(options.animations),
+    };
+    screen.layout = screen.layout();
+    queue!(screen.last_input, P(text("input.nothing")))?;
+    queue!(stdout, EnableMouseCapture)?;
+    // the screen is drawn to a back buffer, then only the cells which changed are sent to the terminal
+    let mut frame = Frame::new(screen.size);
+    screen.redraw(&mut frame)?;
+    // redraw the timer every tick, and show the summary as soon as the game ends
+    loop {
+        if screen.summary.is_none() && screen.game.check_time_limit() {
+            if let Err(error) = bests.record(&screen.game) {
+                screen.summary = Some(fill("summary.best_not_saved", &[("error", &error)]));
+            }
+            screen.draw_summary(&mut frame)?;
+        } else if screen.summary.is_none() {
+            if screen.animations.tick() {
+                screen.draw_changing(&mut frame)?;
+            }
+            screen.draw_timer(&mut frame)?;
+        }
+        frame.present(stdout)?;
+        stdout.flush()?;
+        let Some(read) = poll_input(true, TICK)? else {
+            continue;
+        };
+        if screen.summary.is_some() {
+            if let Input::Resize(width, height) = read {
+                screen.size = (width, height);
+                frame.resize(screen.size);
+                screen.draw_summary(&mut frame)?;
+            } else if let Input::Key(KeyCode::Char(key)) = read {
+                match Action::from_key(key) {
+                    Some(Action::SaveReplay) => {
+                        screen.summary = Some(save_replay(&screen.game));
+                        screen.draw_summary(&mut frame)?;
+                    }
+                    Some(Action::Choose(choice)) => {
+                        queue!(stdout, DisableMouseCapture)?;
+                        return Ok((choice, screen.game));
+                    }
+                    None => {}
+                }
+            }
+            continue;
+        }
+        if let Input::Key(KeyCode::Char('Q')) = read {
+            queue!(stdout, DisableMouseCapture)?;
+            return Ok((Choice::Menu, screen.game));
+        }
+        // only resizing does anything while the terminal is too small to play on
+        if screen.layout.is_none() && !matches!(read, Input::Resize(..)) {
+            continue;
+        }
+        let Screen {
+            game,
+            layout,
+            last_input,
+            cursor,
+            ..
+        } = &mut screen;
+        let direction = match read {
+            Input::Key(KeyCode::Up | KeyCode::Char('K')) => Some(Direction::Up),
+            Input::Key(KeyCode::Down | KeyCode::Char('J')) => Some(Direction::Down),
+            Input::Key(KeyCode::Left | KeyCode::Char('H')) => Some(Direction::Left),
+            Input::Key(KeyCode::Right | KeyCode::Char('L')) => Some(Direction::Right),
+            _ => None,
+        };
+        if let Some(direction) = direction {
+            // the first movement only shows the cursor
+            *cursor =
+                Some(cursor.map_or(0, |index| neighbour(index, game.shown_count(), direction)));
+            screen.relayout(&mut frame)?;
+            continue;
+        }
+        match read {
+            Input::Resize(width, height) => {
+                screen.size = (width, height);
+                // the resized frame is blank, so everything is redrawn even if the layout is unchanged
+                frame.resize(screen.size);
+                screen.layout = screen.layout();
+                screen.redraw(&mut frame)?;
+                continue;
             }
-            if let Some(index) = corresponding_index(char) {
-                match game.select_card(index) {
-                    Err(()) => queue!(stdout, P("Selction out of bounds"))?,
-                    Ok((card, false)) => queue!(stdout, P("Deselected "), card.stylise(false))?,
-                    Ok((card, true)) => {
-                        let styled_card = card.stylise(false);
-                        match game.check_selected_set() {
-                            Some(set_result) => match set_result {
-                                Ok((card1, card2, card3)) => queue!(
-                                    stdout,
-                                    card1.stylise(false),
-                                    S,
-                                    card2.stylise(false),
-                                    S,
-                                    card3.stylise(false),
-                                    S,
-                                    P("form a set!")
-                                )?,
-                                Err((card1, card2, card3)) => queue!(
-                                    stdout,
-                                    card1.stylise(false),
-                                    S,
-                                    card2.stylise(false),
-                                    S,
-                                    card3.stylise(false),
-                                    S,
-                                    P("do not form a set!")
-                                )?,
-                            },
-                            None => queue!(std::io::stdout(), P("Selected "), styled_card)?,
+            Input::Key(key) => {
+                last_input.clear();
+                queue!(last_input, P(key), P(" => "))?;
+                if key == KeyCode::Enter || key == KeyCode::Char(' ') {
+                    match cursor {
+                        Some(index) => select(game, last_input, *index)?,
+                        None => queue!(last_input, P(text("status.move_cursor")))?,
+                    }
+                } else if let KeyCode::Char(char) = key {
+                    if char == '?' && game.config().call_no_set {
+                        queue!(last_input, P(text("status.hints_off")))?;
+                    } else if char == '?' {
+                        match game.hint() {
+                            Some(_) => queue!(last_input, P(text("status.hint")))?,
+                            None => queue!(last_input, P(text("status.no_hint")))?,
+                        }
+                    } else if char == 'N' && options.game.call_no_set {
+                        match game.call_no_set() {
+                            NoSetCall::Correct => {
+                                queue!(last_input, P(text("status.correct_no_set")))?
+                            }
+                            NoSetCall::Wrong(_) => {
+                                queue!(last_input, P(text("status.wrong_no_set")))?
+                            }
                         }
+                    } else if let Some(index) = Keymap::active().index_of(char) {
+                        select(game, last_input, index)?;
+                    } else {
+                        queue!(last_input, P(text("status.no_binding")))?;
+                    }
+                } else if key == KeyCode::Backspace {
+                    match game.pop_last() {
+                        Some(card) => queue!(
+                            last_input,
+                            P(text("status.deselected")),
+                            S,
+                            card.stylise(false),
+                            P(format!(" ({card})"))
+                        )?,
+                        None => queue!(last_input, P(text("status.nothing_selected")))?,
                     }
                 }
-            } else if read.0 == KeyCode::Backspace {
-                match game.pop_last() {
-                    Some(card) => queue!(stdout, P("Deselected "), card.stylise(false))?,
-                    None => queue!(stdout, P("Nothing selected"))?,
+            }
+            // clicks outside of any card are ignored
+            Input::Click(column, row) => {
+                let Some(index) = layout
+                    .as_ref()
+                    .and_then(|layout| layout.card_at(column, row))
+                else {
+                    continue;
+                };
+                last_input.clear();
+                queue!(last_input, P(text("input.click")), P(" => "))?;
+                select(game, last_input, index)?;
+            }
+            Input::RightClick(column, row) => {
+                let Some(index) = layout
+                    .as_ref()
+                    .and_then(|layout| layout.card_at(column, row))
+                else {
+                    continue;
+                };
+                last_input.clear();
+                queue!(last_input, P(text("input.right_click")), P(" => "))?;
+                match game.deselect_card(index) {
+                    Some(card) => queue!(
+                        last_input,
+                        P(text("status.deselected")),
+                        S,
+                        card.stylise(false),
+                        P(format!(" ({card})"))
+                    )?,
+                    None => queue!(last_input, P(text("status.not_selected")))?,
                 }
-            } else {
-                queue!(stdout, P(format!("Key has no known binding")))?;
             }
         }
-        queue!(stdout, MoveTo(0, 1))?;
-        game.print(&mut stdout)?;
+        screen.animations.start(&mut screen.game);
+        screen.relayout(&mut frame)?;
+    }
+}
+
+/// What is printed once the terminal is restored after quitting from the summary
+fn final_remarks(game: &Game) -> String {
+    let ended = game.ended.unwrap_or(game.started);
+    match game.config().time_limit {
+        Some(limit) => fill(
+            "time_attack.over",
+            &[("sets", &game.score.sets), ("time", &format_elapsed(limit))],
+        ),
+        None => format!(
+            "{}{}",
+            text("game.completed"),
+            format_elapsed(ended - game.started).bold()
+        ),
+    }
+}
+
+/// Saves a replay of the game in the current directory, describing where it was saved or why it could not be
+fn save_replay(game: &Game) -> String {
+    let replay = Replay::new(game).expect("games are dealt from a seed");
+    match std::env::current_dir().and_then(|dir| replay.save(&dir)) {
+        Ok(path) => fill("summary.saved", &[("path", &path.display())]),
+        Err(error) => fill("summary.not_saved", &[("error", &error)]),
+    }
+}
+
+/// The keys listed below the keybind table besides those for selecting cards
+fn extra_keys(config: &GameConfig) -> &'static str {
+    if config.call_no_set {
+        text("game.call_no_set_key")
+    } else {
+        text("game.hint_key")
+    }
+}
+
+/// Toggles the selection of a card, checking for a set once three are selected, and describes the result
+fn select(game: &mut Game, out: &mut impl Write, index: usize) -> std::io::Result<()> {
+    match game.select_card(index) {
+        Err(()) => queue!(out, P(text("status.out_of_bounds"))),
+        Ok((card, false)) => queue!(
+            out,
+            P(text("status.deselected")),
+            S,
+            card.stylise(false),
+            P(format!(" ({card})"))
+        ),
+        Ok((card, true)) => {
+            let styled_card = card.stylise(false);
+            let name = card.to_string();
+            match game.check_selected_set() {
+                Some(set_result) => match set_result {
+                    Ok((card1, card2, card3)) => queue!(
+                        out,
+                        card1.stylise(false),
+                        S,
+                        card2.stylise(false),
+                        S,
+                        card3.stylise(false),
+                        S,
+                        P(text("status.form_set")),
+                        P(format!(" ({card1}, {card2}, {card3})"))
+                    ),
+                    Err(mismatch) => {
+                        let (card1, card2, card3) = mismatch.cards;
+                        queue!(
+                            out,
+                            card1.stylise(false),
+                            S,
+                            card2.stylise(false),
+                            S,
+                            card3.stylise(false),
+                            S,
+                            P(text("status.not_set")),
+                            P(format!(" ({card1}, {card2}, {card3})")),
+                            S,
+                            P(fill(
+                                "status.broken",
+                                &[("attributes", &mismatch.describe())]
+                            ))
+                        )
+                    }
+                },
+                None => queue!(
+                    out,
+                    P(text("status.selected")),
+                    S,
+                    styled_card,
+                    P(format!(" ({name})"))
+                ),
+            }
+        }
+    }
+}
+
+/// Everything drawn on the game screen
+struct Screen {
+    game: Game,
+    /// None if the terminal is too small to play on
+    layout: Option<Layout>,
+    size: (u16, u16),
+    card_size: CardSize,
+    /// Width and number of lines of the keybind table
+    keybind_size: (u16, u16),
+    /// Rendered description of the last input and its result
+    last_input: Vec<u8>,
+    /// Index of the card under the keyboard cursor, once the cursor has been moved
+    cursor: Option<usize>,
+    /// The note shown below the summary once the game is over, None while it is being played
+    summary: Option<String>,
+    /// Splits of the personal best from before the game, empty if there was none
+    best: Vec<Duration>,
+    animations: Animations,
+}
+
+impl Screen {
+    /// Recomputes the layout for the current terminal size and number of cards, then draws the screen.
+    /// Only the table and last input are redrawn if the layout is unchanged.
+    fn relayout(&mut self, out: &mut impl Write) -> std::io::Result<()> {
+        // keep the cursor on the table when it shrinks
+        let last_card = self.game.shown_count().saturating_sub(1);
+        self.cursor = self.cursor.map(|index| index.min(last_card));
+        let layout = self.layout();
+        if layout == self.layout {
+            self.draw_changing(out)
+        } else {
+            self.layout = layout;
+            self.redraw(out)
+        }
+    }
+
+    /// Lays the screen out for the current terminal size and number of cards, with the timer after its label
+    fn layout(&self) -> Option<Layout> {
+        let (width, height) = self.size;
+        let layout = Layout::new(
+            width,
+            height,
+            self.game.shown_count(),
+            self.keybind_size,
+            self.card_size,
+        )?;
+        Some(Layout {
+            timer: (self.timer_label().chars().count() as u16, 0),
+            ..layout
+        })
+    }
+
+    /// The label before the timer, which counts down in time attack
+    fn timer_label(&self) -> &'static str {
+        match self.game.config().time_limit {
+            Some(_) => text("game.time_left"),
+            None => text("game.time_elapsed"),
+        }
+    }
+
+    /// Clears the terminal and draws everything except the elapsed time
+    fn redraw(&self, out: &mut impl Write) -> std::io::Result<()> {
+        queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+        let Some(layout) = &self.layout else {
+            let (min_width, min_height) = self.card_size.min_size();
+            return queue!(
+                out,
+                P(fill(
+                    "game.too_small",
+                    &[("width", &min_width), ("height", &min_height)]
+                ))
+            );
+        };
+        queue!(out, PS(self.timer_label().bold()))?;
+        if let Some(row) = layout.keybinds {
+            queue!(
+                out,
+                MoveTo(0, row),
+                P(text("game.keybind_table").bold()),
+                MoveTo(0, row + 1),
+                P(Keymap::active().keybinds(true))
+            )?;
+            queue!(
+                out,
+                MoveTo(0, row + self.keybind_size.1),
+                P(format!("  {}", extra_keys(self.game.config())))
+            )?;
+        }
+        self.draw_changing(out)
+    }
+
+    /// Draws the time taken to find the last set after the timer, and how far ahead of or behind the personal best
+    /// the game is at that set
+    fn draw_split(&self, out: &mut impl Write, layout: &Layout) -> std::io::Result<()> {
+        let column = layout.timer.0 + TIMER_WIDTH + 2;
+        queue!(
+            out,
+            MoveTo(column, layout.timer.1),
+            Clear(ClearType::UntilNewLine)
+        )?;
+        let (Some(split), Some(lap)) = (self.game.splits.last(), laps(&self.game.splits).pop())
+        else {
+            return Ok(());
+        };
+        let set = self.game.splits.len();
+        let text = fill(
+            "game.split",
+            &[
+                ("set", &set),
+                ("lap", &format!("{:.1}s", lap.as_secs_f32())),
+            ],
+        );
+        let best = self.best.get(set - 1);
+        // "  -12.3s" after the lap, if there is a personal best
+        let width = text.chars().count() as u16 + if best.is_some() { 8 } else { 0 };
+        if column + width > layout.width {
+            return Ok(());
+        }
+        queue!(out, P(text))?;
+        if let Some(best) = best {
+            queue!(out, P("  "), PS(compare(*split, *best)))?;
+        }
+        Ok(())
+    }
+
+    /// Draws the time elapsed, or the time left in time attack
+    fn draw_timer(&self, out: &mut impl Write) -> std::io::Result<()> {
+        let Some(layout) = &self.layout else {
+            return Ok(());
+        };
+        let time = match self.game.time_left() {
+            Some(time_left) => time_left,
+            None => self.game.started.elapsed(),
+        };
+        queue!(
+            out,
+            MoveTo(layout.timer.0, layout.timer.1),
+            P(format_elapsed(time))
+        )
+    }
+
+    /// Shows the summary of the finished game, with the note below it
+    fn draw_summary(&mut self, out: &mut impl Write) -> std::io::Result<()> {
+        let note = self.summary.get_or_insert_with(String::new);
+        summary::draw(out, &self.game, self.size, &self.best, note)
+    }
+
+    /// Draws the table and last input
+    fn draw_changing(&self, out: &mut impl Write) -> std::io::Result<()> {
+        let Some(layout) = &self.layout else {
+            return Ok(());
+        };
+        self.game.print(out, layout, self.cursor)?;
+        self.animations.draw(out, layout, &self.game, self.cursor)?;
+        self.draw_split(out, layout)?;
+        // the last input is the bottom of the screen, and long descriptions wrap onto the rows below it
+        queue!(
+            out,
+            MoveTo(0, layout.last_input),
+            Clear(ClearType::FromCursorDown),
+            PS(text("game.last_input").bold())
+        )?;
+        out.write_all(&self.last_input)
     }
 }
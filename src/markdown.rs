@@ -0,0 +1,45 @@
+/// Renders the subset of markdown used by the tutorial with ANSI escape codes:
+/// headings and **bold** text are bold, *emphasis* is italic and `code` is dim.
+/// A backslash at the end of a line (a markdown line break) is dropped, as lines are printed as written.
+pub fn to_ansi(markdown: &str) -> String {
+    markdown
+        .lines()
+        .map(|line| {
+            let line = line.strip_suffix('\\').map_or(line, str::trim_end);
+            let heading = line.trim_start_matches('#');
+            if heading.len() < line.len() && heading.starts_with(' ') {
+                format!("\x1b[1m{}\x1b[0m", inline(heading.trim_start()))
+            } else {
+                inline(line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders emphasis, bold and code spans within a line
+fn inline(line: &str) -> String {
+    let mut out = String::new();
+    let (mut bold, mut italic, mut code) = (false, false, false);
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if !code => out.extend(chars.next()),
+            '`' => {
+                code = !code;
+                out.push_str(if code { "\x1b[2m" } else { "\x1b[22m" });
+            }
+            '*' if !code && chars.peek() == Some(&'*') => {
+                chars.next();
+                bold = !bold;
+                out.push_str(if bold { "\x1b[1m" } else { "\x1b[22m" });
+            }
+            '*' if !code => {
+                italic = !italic;
+                out.push_str(if italic { "\x1b[3m" } else { "\x1b[23m" });
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
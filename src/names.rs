@@ -0,0 +1,78 @@
+use crate::{
+    locale::Locale,
+    theme::{ColourNames, Theme},
+};
+
+/// Words used to describe cards in one language.
+/// Arrays are indexed in the order of the attribute enums in `card`, and pairs are singular then plural.
+pub struct CardNames {
+    /// Pills, diamonds and squiggles
+    pub shapes: [[&'static str; 2]; 3],
+    /// One, two and three
+    pub counts: [&'static str; 3],
+    /// Red, green and purple
+    pub colours: [[&'static str; 2]; 3],
+    /// Blue, orange and pink, the colours of the colour-blind theme
+    pub okabe_ito_colours: [[&'static str; 2]; 3],
+    /// No underline, one underline and two underlines
+    pub decorations: [[&'static str; 2]; 3],
+    /// Shape, number, decoration and colour
+    pub attributes: [&'static str; 4],
+    /// How a card is described, where {count}, {colour}, {decoration} and {shape} are replaced by the names above
+    pub card: &'static str,
+}
+
+pub static ENGLISH: CardNames = CardNames {
+    shapes: [
+        ["pill", "pills"],
+        ["diamond", "diamonds"],
+        ["squiggle", "squiggles"],
+    ],
+    counts: ["one", "two", "three"],
+    colours: [["red", "red"], ["green", "green"], ["purple", "purple"]],
+    okabe_ito_colours: [["blue", "blue"], ["orange", "orange"], ["pink", "pink"]],
+    decorations: [
+        ["plain", "plain"],
+        ["underlined", "underlined"],
+        ["double-underlined", "double-underlined"],
+    ],
+    attributes: ["shape", "number", "decoration", "colour"],
+    card: "{count} {colour} {decoration} {shape}",
+};
+
+pub static FRENCH: CardNames = CardNames {
+    shapes: [
+        ["ovale", "ovales"],
+        ["losange", "losanges"],
+        ["serpentin", "serpentins"],
+    ],
+    counts: ["un", "deux", "trois"],
+    colours: [
+        ["rouge", "rouges"],
+        ["vert", "verts"],
+        ["violet", "violets"],
+    ],
+    okabe_ito_colours: [["bleu", "bleus"], ["orange", "orange"], ["rose", "roses"]],
+    decorations: [
+        ["simple", "simples"],
+        ["souligné", "soulignés"],
+        ["doublement souligné", "doublement soulignés"],
+    ],
+    attributes: ["forme", "nombre", "décoration", "couleur"],
+    card: "{count} {shape} {colour} {decoration}",
+};
+
+impl CardNames {
+    /// The names used to describe cards in the active locale
+    pub fn active() -> &'static Self {
+        Locale::active().names
+    }
+
+    /// The names of the colours cards are drawn in with the given theme
+    pub fn colour_names(&self, theme: &Theme) -> &[[&'static str; 2]; 3] {
+        match theme.colour_names {
+            ColourNames::Standard => &self.colours,
+            ColourNames::OkabeIto => &self.okabe_ito_colours,
+        }
+    }
+}
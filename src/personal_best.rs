@@ -0,0 +1,209 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crossterm::style::{StyledContent, Stylize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{Game, GameConfig},
+    locale::{fill, text},
+};
+
+/// The fastest game played with one table setup
+#[derive(Serialize, Deserialize)]
+struct Run {
+    table_size: usize,
+    extra_draw: usize,
+    refill_extras: bool,
+    call_no_set: bool,
+    /// Milliseconds since the start of the game at which each set was found
+    splits: Vec<u64>,
+}
+
+impl Run {
+    fn matches(&self, config: &GameConfig) -> bool {
+        self.table_size == config.table_size
+            && self.extra_draw == config.extra_draw
+            && self.refill_extras == config.refill_extras
+            && self.call_no_set == config.call_no_set
+    }
+}
+
+/// Split times of the fastest game through the whole deck with each table setup, kept in `personal_best.toml`
+/// next to the config file. Time-attack games are not recorded, as they always take the same time.
+#[derive(Serialize, Deserialize, Default)]
+pub struct PersonalBests {
+    /// Where the runs are saved, or None to keep them only until the program exits
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(default)]
+    runs: Vec<Run>,
+}
+
+impl PersonalBests {
+    /// Reads the personal bests stored next to the config file at `config_path`.
+    /// A missing file is treated as having no personal bests. A file which cannot be read is left alone,
+    /// new personal bests being kept only until the program exits, and the problem is returned.
+    pub fn load(config_path: Option<&Path>) -> (Self, Option<String>) {
+        let Some(path) = config_path.map(|path| path.with_file_name("personal_best.toml")) else {
+            return (Self::default(), None);
+        };
+        let bests = match std::fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str::<Self>(&contents)
+                .map_err(|error| error.message().to_owned())
+                .and_then(|bests| {
+                    let ascending =
+                        |run: &Run| run.splits.windows(2).all(|pair| pair[0] <= pair[1]);
+                    if bests.runs.iter().all(ascending) {
+                        Ok(bests)
+                    } else {
+                        Err(text("bests.out_of_order").to_owned())
+                    }
+                }),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.to_string()),
+        };
+        match bests {
+            Ok(bests) => (
+                Self {
+                    path: Some(path),
+                    ..bests
+                },
+                None,
+            ),
+            Err(error) => {
+                let error = fill(
+                    "bests.unreadable",
+                    &[("path", &path.display()), ("error", &error)],
+                );
+                (Self::default(), Some(error))
+            }
+        }
+    }
+
+    /// Split times of the personal best with the given table setup, or an empty list if there is none
+    pub fn splits(&self, config: &GameConfig) -> Vec<Duration> {
+        self.runs
+            .iter()
+            .find(|run| run.matches(config))
+            .map_or_else(Vec::new, |run| {
+                run.splits
+                    .iter()
+                    .map(|&millis| Duration::from_millis(millis))
+                    .collect()
+            })
+    }
+
+    /// Whether a finished game beats the personal best for its table setup, finishing sooner.
+    /// Games which were not played through the whole deck never do.
+    pub fn is_beaten_by(best: &[Duration], game: &Game) -> bool {
+        let finished = game.ended.is_some() && game.config().time_limit.is_none();
+        match (game.splits.last(), best.last()) {
+            (Some(time), Some(best)) => finished && time < best,
+            (Some(_), None) => finished,
+            (None, _) => false,
+        }
+    }
+
+    /// Keeps the splits of a finished game if it beats the personal best, saving them to the file.
+    /// Returns whether it was a new personal best.
+    pub fn record(&mut self, game: &Game) -> std::io::Result<bool> {
+        let config = game.config();
+        if !Self::is_beaten_by(&self.splits(config), game) {
+            return Ok(false);
+        }
+        self.runs.retain(|run| !run.matches(config));
+        self.runs.push(Run {
+            table_size: config.table_size,
+            extra_draw: config.extra_draw,
+            refill_extras: config.refill_extras,
+            call_no_set: config.call_no_set,
+            splits: game
+                .splits
+                .iter()
+                .map(|split| split.as_millis() as u64)
+                .collect(),
+        });
+        let Some(path) = &self.path else {
+            return Ok(true);
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, text)?;
+        Ok(true)
+    }
+}
+
+/// Time taken to find each set, from the time since the start at which each was found
+pub fn laps(splits: &[Duration]) -> Vec<Duration> {
+    let previous = std::iter::once(Duration::ZERO).chain(splits.iter().copied());
+    splits
+        .iter()
+        .zip(previous)
+        .map(|(split, previous)| split.saturating_sub(previous))
+        .collect()
+}
+
+/// How far ahead of (green, negative) or behind (red, positive) the personal best a time is, like a speedrun timer
+pub fn compare(time: Duration, best: Duration) -> StyledContent<String> {
+    if time < best {
+        format!("-{:.1}s", (best - time).as_secs_f32()).green()
+    } else {
+        format!("+{:.1}s", (time - best).as_secs_f32()).red()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Loads the personal bests from a file with the given contents, returning whether they would be saved
+    fn load(contents: &str) -> (bool, Option<String>) {
+        static LOADED: AtomicUsize = AtomicUsize::new(0);
+        let count = LOADED.fetch_add(1, Ordering::Relaxed);
+        let dir =
+            std::env::temp_dir().join(format!("rust-set-bests-{}-{count}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("personal_best.toml");
+        std::fs::write(&path, contents).unwrap();
+        let (bests, error) = PersonalBests::load(Some(&dir.join("config.toml")));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+        std::fs::remove_dir_all(&dir).unwrap();
+        (bests.path.is_some(), error)
+    }
+
+    const RUN: &str =
+        "table_size = 12\nextra_draw = 3\nrefill_extras = false\ncall_no_set = false\n";
+
+    #[test]
+    fn valid_files_are_loaded() {
+        let (saved, error) = load(&format!("[[runs]]\n{RUN}splits = [1000, 5000]\n"));
+        assert!(saved);
+        assert_eq!(error, None);
+    }
+
+    #[test]
+    fn unreadable_files_are_reported_and_kept() {
+        for contents in [
+            "[[runs]]\ntable_size = \"twelve\"\n".to_owned(),
+            format!("[[runs]]\n{RUN}splits = [5000, 1000]\n"),
+        ] {
+            let (saved, error) = load(&contents);
+            assert!(!saved, "{contents}");
+            assert!(error.is_some(), "{contents}");
+        }
+    }
+
+    #[test]
+    fn laps_never_go_below_zero() {
+        let splits = [5000, 1000, 3000].map(Duration::from_millis);
+        let laps = laps(&splits).into_iter().map(|lap| lap.as_millis());
+        assert_eq!(laps.collect::<Vec<_>>(), [5000, 0, 2000]);
+    }
+}
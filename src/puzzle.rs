@@ -0,0 +1,331 @@
+use std::{io::Write, time::Instant};
+
+use crossterm::{
+    cursor::MoveTo,
+    event::KeyCode,
+    queue,
+    style::{Color, Print as P, PrintStyledContent as PS, Stylize},
+    terminal::{Clear, ClearType},
+};
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::{
+    bitset::CardSet,
+    card::{Attribute, Card},
+    game::print_table,
+    keymap::Keymap,
+    layout::{CardSize, Layout},
+    locale::{fill, text},
+    render::Frame,
+    util::{format_elapsed, poll_input, Input, TICK},
+};
+
+/// Number of cards on a puzzle board
+pub const BOARD_SIZE: usize = 12;
+/// Number of sets on a board unless another is chosen, as in the classic daily puzzle
+pub const DEFAULT_SETS: usize = 6;
+/// Most sets a board can have: no 12 cards hold more than 14 sets
+pub const MAX_SETS: usize = 14;
+
+/// The outcome of guessing three cards
+pub enum Guess {
+    /// A set which had not been found yet, as ascending indices
+    Found([usize; 3]),
+    /// A set which had already been found
+    AlreadyFound([usize; 3]),
+    /// Not a set, because of these attributes
+    NotASet(Vec<Attribute>),
+}
+
+/// A fixed board with a known number of sets, all of which must be found
+pub struct Puzzle {
+    pub cards: Vec<Card>,
+    /// Number of sets on the board
+    pub sets: usize,
+    /// Sets found so far, in the order found, as ascending indices into `cards`
+    pub found: Vec<[usize; 3]>,
+    pub selection: Vec<usize>,
+    /// Number of guesses which were not sets
+    pub mistakes: usize,
+}
+
+impl Puzzle {
+    /// Generates a board with exactly `sets` sets, which must be at most `MAX_SETS`.
+    /// Starts from random cards and swaps single cards, keeping swaps which do not move the count away from the target.
+    pub fn generate(rng: &mut impl Rng, sets: usize) -> Self {
+        let count = |board: &[u8]| board.iter().copied().collect::<CardSet>().count_sets();
+        let tiles = 'search: loop {
+            let mut board = (0u8..81).choose_multiple(rng, BOARD_SIZE);
+            let mut current = count(&board);
+            for _ in 0..10_000 {
+                if current == sets {
+                    break 'search board;
+                }
+                let index = rng.gen_range(0..BOARD_SIZE);
+                let previous = board[index];
+                board[index] = (0u8..81)
+                    .filter(|tile| !board.contains(tile))
+                    .choose(rng)
+                    .unwrap();
+                let next = count(&board);
+                if next.abs_diff(sets) <= current.abs_diff(sets) {
+                    current = next;
+                } else {
+                    board[index] = previous;
+                }
+            }
+        };
+        Self {
+            cards: tiles
+                .into_iter()
+                .map(|tile| Card::from_tile_number(tile).unwrap())
+                .collect(),
+            sets,
+            found: Vec::new(),
+            selection: Vec::new(),
+            mistakes: 0,
+        }
+    }
+
+    /// Toggles the selection of a card. Returns whether it is now selected, or None if there is no card at index.
+    pub fn toggle(&mut self, index: usize) -> Option<bool> {
+        if index >= self.cards.len() {
+            return None;
+        }
+        match self
+            .selection
+            .iter()
+            .position(|selected| *selected == index)
+        {
+            Some(position) => {
+                self.selection.remove(position);
+                Some(false)
+            }
+            None => {
+                self.selection.push(index);
+                Some(true)
+            }
+        }
+    }
+
+    /// Deselects the most recently selected card
+    pub fn pop_last(&mut self) -> Option<&Card> {
+        self.selection.pop().map(|index| &self.cards[index])
+    }
+
+    /// Checks the selection once three cards are selected, clearing it
+    pub fn guess(&mut self) -> Option<Guess> {
+        let [a, b, c] = self.selection[..] else {
+            return None;
+        };
+        self.selection.clear();
+        let mut set = [a, b, c];
+        set.sort_unstable();
+        let broken = Card::broken_attributes(set.map(|index| &self.cards[index]));
+        Some(if !broken.is_empty() {
+            self.mistakes += 1;
+            Guess::NotASet(broken)
+        } else if self.found.contains(&set) {
+            Guess::AlreadyFound(set)
+        } else {
+            self.found.push(set);
+            Guess::Found(set)
+        })
+    }
+
+    pub fn solved(&self) -> bool {
+        self.found.len() == self.sets
+    }
+}
+
+/// Plays a puzzle until every set is found or Q is pressed, then returns to the menu
+pub fn play(out: &mut impl Write, sets: usize, card_size: CardSize) -> std::io::Result<()> {
+    let mut puzzle = Puzzle::generate(&mut rand::thread_rng(), sets);
+    let keymap = Keymap::active();
+    let keybind_size = keymap.keybinds_size(false);
+    let started = Instant::now();
+    let mut size = crossterm::terminal::size()?;
+    let mut status = text("input.nothing").to_owned();
+    let mut finished = None;
+    // the whole screen is drawn to a back buffer every tick, and only the cells which changed are sent
+    let mut frame = Frame::new(size);
+    loop {
+        let layout = Layout::new(size.0, size.1, BOARD_SIZE, keybind_size, card_size);
+        redraw(&mut frame, layout.as_ref(), card_size)?;
+        if let Some(layout) = &layout {
+            draw_board(&mut frame, layout, &puzzle, &status)?;
+            let elapsed = finished.unwrap_or_else(|| started.elapsed());
+            queue!(
+                frame,
+                MoveTo(layout.timer.0, layout.timer.1),
+                P(format_elapsed(elapsed))
+            )?;
+        }
+        frame.present(out)?;
+        out.flush()?;
+        let Some(input) = poll_input(true, TICK)? else {
+            continue;
+        };
+        if finished.is_some() {
+            match input {
+                Input::Key(_) => return Ok(()),
+                Input::Resize(width, height) => {
+                    size = (width, height);
+                    frame.resize(size);
+                }
+                _ => {}
+            }
+            continue;
+        }
+        let index = match input {
+            Input::Resize(width, height) => {
+                size = (width, height);
+                frame.resize(size);
+                continue;
+            }
+            Input::Key(KeyCode::Char('Q')) => return Ok(()),
+            Input::Key(KeyCode::Backspace) => {
+                status = match puzzle.pop_last() {
+                    Some(card) => format!("{} {card}", text("status.deselected")),
+                    None => text("status.nothing_selected").to_owned(),
+                };
+                continue;
+            }
+            Input::Key(KeyCode::Char(key)) => keymap.index_of(key),
+            Input::Click(column, row) => layout
+                .as_ref()
+                .and_then(|layout| layout.card_at(column, row)),
+            _ => None,
+        };
+        let Some(index) = index else {
+            continue;
+        };
+        status = match puzzle.toggle(index) {
+            None => text("status.out_of_bounds").to_owned(),
+            Some(false) => format!("{} {}", text("status.deselected"), puzzle.cards[index]),
+            Some(true) => match puzzle.guess() {
+                None => format!("{} {}", text("status.selected"), puzzle.cards[index]),
+                Some(Guess::Found(_)) if puzzle.solved() => {
+                    let elapsed = started.elapsed();
+                    finished = Some(elapsed);
+                    fill(
+                        "puzzle.solved",
+                        &[("sets", &puzzle.sets), ("time", &format_elapsed(elapsed))],
+                    )
+                }
+                Some(Guess::Found(_)) => text("puzzle.found").to_owned(),
+                Some(Guess::AlreadyFound(_)) => text("puzzle.duplicate").to_owned(),
+                Some(Guess::NotASet(broken)) => {
+                    let broken = broken.iter().map(ToString::to_string).collect::<Vec<_>>();
+                    format!(
+                        "{} {}",
+                        text("status.not_set"),
+                        fill("status.broken", &[("attributes", &broken.join(", "))])
+                    )
+                }
+            },
+        };
+    }
+}
+
+/// Clears the terminal and draws the parts of the screen which do not change during a puzzle
+fn redraw(
+    out: &mut impl Write,
+    layout: Option<&Layout>,
+    card_size: CardSize,
+) -> std::io::Result<()> {
+    queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+    let Some(layout) = layout else {
+        let (min_width, min_height) = card_size.min_size();
+        return queue!(
+            out,
+            P(fill(
+                "game.too_small",
+                &[("width", &min_width), ("height", &min_height)]
+            ))
+        );
+    };
+    queue!(out, PS(text("game.time_elapsed").bold()))?;
+    if let Some(row) = layout.keybinds {
+        queue!(
+            out,
+            MoveTo(0, row),
+            P(text("game.keybind_table").bold()),
+            MoveTo(0, row + 1),
+            P(Keymap::active().keybinds(false))
+        )?;
+    }
+    Ok(())
+}
+
+/// Draws the board, the number of sets found, the status line and as many found sets as fit below it
+fn draw_board(
+    out: &mut impl Write,
+    layout: &Layout,
+    puzzle: &Puzzle,
+    status: &str,
+) -> std::io::Result<()> {
+    print_table(out, layout, &puzzle.cards, None, |index| {
+        puzzle.selection.contains(&index).then_some(Color::DarkGrey)
+    })?;
+    queue!(
+        out,
+        MoveTo(0, layout.pile),
+        P(fill(
+            "puzzle.progress",
+            &[("found", &puzzle.found.len()), ("sets", &puzzle.sets)]
+        )),
+        Clear(ClearType::UntilNewLine),
+        MoveTo(0, layout.last_input),
+        PS(text("game.last_input").bold()),
+        P(status),
+        Clear(ClearType::UntilNewLine)
+    )?;
+    for (row, set) in (layout.last_input + 1..layout.height).zip(&puzzle.found) {
+        queue!(out, MoveTo(0, row), P("  "))?;
+        for index in set {
+            queue!(out, puzzle.cards[*index].stylise(false), P(" "))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn boards_have_exactly_the_chosen_number_of_sets() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for sets in 1..=MAX_SETS {
+            let puzzle = Puzzle::generate(&mut rng, sets);
+            let tiles = puzzle.cards.iter().collect::<CardSet>();
+            assert_eq!(tiles.len() as usize, BOARD_SIZE, "cards repeat");
+            assert_eq!(tiles.count_sets(), sets);
+        }
+    }
+
+    #[test]
+    fn sets_can_only_be_found_once() {
+        let mut puzzle = Puzzle::generate(&mut StdRng::seed_from_u64(1), 1);
+        let tiles = puzzle.cards.iter().collect::<CardSet>();
+        let set = tiles.sets().next().unwrap().map(|tile| {
+            let tile = Card::from_tile_number(tile).unwrap();
+            puzzle.cards.iter().position(|card| *card == tile).unwrap()
+        });
+        let guess = |puzzle: &mut Puzzle| {
+            set.iter().rev().for_each(|index| {
+                puzzle.toggle(*index);
+            });
+            puzzle.guess()
+        };
+        let mut sorted = set;
+        sorted.sort_unstable();
+        assert!(matches!(guess(&mut puzzle), Some(Guess::Found(found)) if found == sorted));
+        assert!(puzzle.solved());
+        assert!(matches!(guess(&mut puzzle), Some(Guess::AlreadyFound(found)) if found == sorted));
+        assert_eq!(puzzle.found.len(), 1);
+        assert_eq!(puzzle.mistakes, 0);
+    }
+}
@@ -0,0 +1,418 @@
+use std::{fmt::Write as _, io::Write};
+
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    terminal::{BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate},
+};
+
+/// A colour set by an SGR sequence
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+enum Colour {
+    #[default]
+    Default,
+    /// One of the 16 named colours, as its offset from the first foreground code (0-7 or 60-67)
+    Named(u8),
+    /// A colour from the 256 colour palette
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Colour {
+    /// Reads the rest of a `38`, `48` or `58` parameter, e.g. `5;9` or `2;255;0;0`
+    fn extended<'a>(params: &mut impl Iterator<Item = &'a str>) -> Self {
+        let mut next = || params.next().and_then(|param| param.parse().ok());
+        match next() {
+            Some(5) => next().map_or(Self::Default, Self::Indexed),
+            Some(2) => match (next(), next(), next()) {
+                (Some(r), Some(g), Some(b)) => Self::Rgb(r, g, b),
+                _ => Self::Default,
+            },
+            _ => Self::Default,
+        }
+    }
+
+    /// Appends the parameters setting this colour, where `kind` is 3 for text, 4 for the background and 5 for underlines
+    fn write_params(self, kind: u8, sgr: &mut String) {
+        let _ = match self {
+            Self::Default => Ok(()),
+            Self::Named(offset) => write!(sgr, ";{}", kind * 10 + offset),
+            Self::Indexed(index) => write!(sgr, ";{kind}8;5;{index}"),
+            Self::Rgb(r, g, b) => write!(sgr, ";{kind}8;2;{r};{g};{b}"),
+        };
+    }
+}
+
+/// How a cell is styled, kept in a canonical form so that cells styled the same way compare equal
+/// however they were styled
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+struct Style {
+    /// Bit n is set if the attribute with SGR code n (1-3 and 5-9, e.g. 1 for bold) is on
+    attributes: u16,
+    /// 0 for no underline, 4 for a single line, 21 for a double line or 40 + n for the underline style `4:n`
+    underline: u8,
+    foreground: Colour,
+    background: Colour,
+    underline_colour: Colour,
+}
+
+impl Style {
+    /// Applies the parameters of an SGR (`ESC [ ... m`) sequence. Unsupported parameters are ignored.
+    fn apply(&mut self, params: &str) {
+        let mut params = params.split(';');
+        while let Some(param) = params.next() {
+            match param {
+                "" | "0" => *self = Self::default(),
+                "4" | "21" => self.underline = param.parse().unwrap(),
+                "22" => self.attributes &= !(1 << 1 | 1 << 2),
+                "24" => self.underline = 0,
+                "25" => self.attributes &= !(1 << 5 | 1 << 6),
+                "23" | "27" | "28" | "29" => {
+                    self.attributes &= !(1 << (param.parse::<u8>().unwrap() - 20))
+                }
+                "38" => self.foreground = Colour::extended(&mut params),
+                "48" => self.background = Colour::extended(&mut params),
+                "58" => self.underline_colour = Colour::extended(&mut params),
+                "39" => self.foreground = Colour::Default,
+                "49" => self.background = Colour::Default,
+                "59" => self.underline_colour = Colour::Default,
+                _ => {
+                    if let Some(style) = param.strip_prefix("4:") {
+                        self.underline = match style.parse::<u8>() {
+                            Ok(0) | Err(_) => 0,
+                            Ok(style) => 40 + style,
+                        };
+                        continue;
+                    }
+                    match param.parse::<u8>() {
+                        Ok(code @ (1..=3 | 5..=9)) => self.attributes |= 1 << code,
+                        Ok(code @ (30..=37 | 90..=97)) => {
+                            self.foreground = Colour::Named(code - 30)
+                        }
+                        Ok(code @ (40..=47 | 100..=107)) => {
+                            self.background = Colour::Named(code - 40)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// The SGR sequence which resets the style and then sets this one
+    fn sgr(&self) -> String {
+        let mut sgr = String::from("\x1b[0");
+        for code in (1..=9).filter(|code| self.attributes & 1 << code != 0) {
+            let _ = write!(sgr, ";{code}");
+        }
+        let _ = match self.underline {
+            0 => Ok(()),
+            40.. => write!(sgr, ";4:{}", self.underline - 40),
+            underline => write!(sgr, ";{underline}"),
+        };
+        self.foreground.write_params(3, &mut sgr);
+        self.background.write_params(4, &mut sgr);
+        self.underline_colour.write_params(5, &mut sgr);
+        sgr.push('m');
+        sgr
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Cell {
+    symbol: char,
+    style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            symbol: ' ',
+            style: Style::default(),
+        }
+    }
+}
+
+/// A back buffer the size of the terminal. Screens are drawn to it as they would be to the terminal,
+/// with the cursor movement, clearing and styling sequences crossterm queues, then [`Frame::present`]
+/// sends only the cells which changed since the last frame, so redrawing an unchanged screen does not flicker.
+/// Other escape sequences (such as mouse capture) are ignored, so must be sent to the terminal directly.
+pub struct Frame {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+    /// The cells as last presented, or None if the terminal must be cleared and drawn from scratch
+    shown: Option<Vec<Cell>>,
+    cursor: (u16, u16),
+    style: Style,
+    /// The start of an escape sequence or character cut off at the end of the last write
+    pending: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new((width, height): (u16, u16)) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); usize::from(width) * usize::from(height)],
+            shown: None,
+            cursor: (0, 0),
+            style: Style::default(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Resizes the frame to the new size of the terminal, blanking it. The next frame is drawn from scratch.
+    pub fn resize(&mut self, size: (u16, u16)) {
+        *self = Self::new(size);
+    }
+
+    /// Sends the cells which changed since the last frame to the terminal, as one synchronized update
+    /// on terminals which support them. The frame is kept, to be drawn over for the next one.
+    pub fn present(&mut self, out: &mut impl Write) -> std::io::Result<()> {
+        queue!(out, BeginSynchronizedUpdate)?;
+        let shown = match self.shown.take() {
+            Some(shown) => shown,
+            None => {
+                queue!(out, Clear(ClearType::All))?;
+                vec![Cell::default(); self.cells.len()]
+            }
+        };
+        let width = usize::from(self.width);
+        let (mut position, mut style) = (None, Style::default());
+        for (index, (cell, old)) in self.cells.iter().zip(&shown).enumerate() {
+            if cell == old {
+                continue;
+            }
+            let (x, y) = ((index % width) as u16, (index / width) as u16);
+            if position != Some((x, y)) {
+                queue!(out, MoveTo(x, y))?;
+            }
+            if cell.style != style || position.is_none() {
+                out.write_all(cell.style.sgr().as_bytes())?;
+                style = cell.style;
+            }
+            write!(out, "{}", cell.symbol)?;
+            position = Some((x + 1, y));
+        }
+        if position.is_some() {
+            out.write_all(b"\x1b[0m")?;
+        }
+        self.shown = Some(self.cells.clone());
+        queue!(out, EndSynchronizedUpdate)
+    }
+
+    fn index(&self, (x, y): (u16, u16)) -> usize {
+        usize::from(y) * usize::from(self.width) + usize::from(x.min(self.width))
+    }
+
+    /// Blanks the cells from `start` up to but not including `end`, both clamped to the frame
+    fn clear(&mut self, start: usize, end: usize) {
+        let end = end.min(self.cells.len());
+        if let Some(cells) = self.cells.get_mut(start.min(end)..end) {
+            cells.fill(Cell::default());
+        }
+    }
+
+    /// Puts a character at the cursor, wrapping to the next line past the right edge as terminals do.
+    /// Characters below the bottom of the frame are dropped.
+    fn print(&mut self, symbol: char) {
+        if self.cursor.0 >= self.width {
+            self.cursor = (0, self.cursor.1.saturating_add(1));
+        }
+        if self.cursor.1 < self.height {
+            let index = self.index(self.cursor);
+            self.cells[index] = Cell {
+                symbol,
+                style: self.style,
+            };
+        }
+        self.cursor.0 += 1;
+    }
+
+    /// The column cursor movements stop at
+    fn last_column(&self) -> u16 {
+        self.width.saturating_sub(1)
+    }
+
+    /// Carries out a CSI (`ESC [`) sequence with the given parameters and final byte
+    fn control(&mut self, params: &str, action: u8) {
+        let count = || params.parse::<u16>().unwrap_or(1).max(1);
+        let (x, y) = self.cursor;
+        let line = self.index((0, y));
+        match action {
+            b'H' | b'f' => {
+                let mut params = params.split(';').map(|param| param.parse::<u16>());
+                let row = params.next().and_then(Result::ok).unwrap_or(1).max(1);
+                let column = params.next().and_then(Result::ok).unwrap_or(1).max(1);
+                // like terminals, stop at the right edge rather than wrapping
+                self.cursor = ((column - 1).min(self.last_column()), row - 1);
+            }
+            b'A' => self.cursor.1 = y.saturating_sub(count()),
+            b'B' => self.cursor.1 = y.saturating_add(count()),
+            b'C' => self.cursor.0 = x.saturating_add(count()).min(self.last_column()),
+            b'D' => self.cursor.0 = x.saturating_sub(count()),
+            b'E' => self.cursor = (0, y.saturating_add(count())),
+            b'F' => self.cursor = (0, y.saturating_sub(count())),
+            b'G' => self.cursor.0 = (count() - 1).min(self.last_column()),
+            b'J' => match params {
+                "1" => self.clear(0, self.index(self.cursor) + 1),
+                "2" | "3" => self.clear(0, self.cells.len()),
+                _ => self.clear(self.index(self.cursor), self.cells.len()),
+            },
+            b'K' if y < self.height => match params {
+                "1" => self.clear(line, self.index(self.cursor) + 1),
+                "2" => self.clear(line, line + usize::from(self.width)),
+                _ => self.clear(self.index(self.cursor), line + usize::from(self.width)),
+            },
+            b'm' => self.style.apply(params),
+            _ => {}
+        }
+    }
+
+    /// Interprets as much of the pending output as is complete
+    fn interpret(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        let mut rest = &pending[..];
+        while let Some(&byte) = rest.first() {
+            let used = match byte {
+                0x1b => match rest.get(1) {
+                    None => break,
+                    Some(b'[') => {
+                        // parameter and intermediate bytes, then the final byte
+                        let Some(end) = rest[2..]
+                            .iter()
+                            .position(|byte| (0x40..0x7f).contains(byte))
+                        else {
+                            break;
+                        };
+                        let params = std::str::from_utf8(&rest[2..2 + end]).unwrap_or_default();
+                        // private sequences such as `ESC [ ? 25 l` don't affect what is shown
+                        if !params.starts_with('?') {
+                            self.control(params, rest[2 + end]);
+                        }
+                        3 + end
+                    }
+                    Some(_) => 2,
+                },
+                b'\r' => {
+                    self.cursor.0 = 0;
+                    1
+                }
+                // the terminal is in raw mode, so a newline only moves down
+                b'\n' => {
+                    self.cursor.1 = self.cursor.1.saturating_add(1);
+                    1
+                }
+                ..=0x1f | 0x7f => 1,
+                _ => {
+                    let length = match byte {
+                        0xf0.. => 4,
+                        0xe0.. => 3,
+                        0xc0.. => 2,
+                        _ => 1,
+                    };
+                    if rest.len() < length {
+                        break;
+                    }
+                    match std::str::from_utf8(&rest[..length]) {
+                        Ok(text) => {
+                            text.chars().for_each(|symbol| self.print(symbol));
+                            length
+                        }
+                        // skip a stray byte
+                        Err(_) => 1,
+                    }
+                }
+            };
+            rest = &rest[used..];
+        }
+        self.pending = rest.to_vec();
+    }
+}
+
+impl Write for Frame {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        self.interpret();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::style::{Print as P, PrintStyledContent as PS, Stylize};
+
+    fn present(frame: &mut Frame) -> String {
+        let mut out = Vec::new();
+        frame.present(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn only_changed_cells_are_sent() {
+        let mut frame = Frame::new((20, 3));
+        queue!(frame, MoveTo(2, 1), PS("00:01".bold().red())).unwrap();
+        assert!(present(&mut frame).contains("00:01"));
+        // redrawing the same screen from scratch sends nothing but the update markers
+        queue!(
+            frame,
+            Clear(ClearType::All),
+            MoveTo(2, 1),
+            PS("00:01".bold().red())
+        )
+        .unwrap();
+        let unchanged = present(&mut frame);
+        assert!(!unchanged.contains("00"), "{unchanged:?}");
+        queue!(frame, MoveTo(2, 1), PS("00:02".bold().red())).unwrap();
+        let changed = present(&mut frame);
+        assert!(
+            changed.contains("\x1b[2;7H\x1b[0;1;38;5;9m2"),
+            "{changed:?}"
+        );
+        assert!(!changed.contains("00:0"), "{changed:?}");
+    }
+
+    #[test]
+    fn styles_compare_equal_however_they_were_set() {
+        let mut frame = Frame::new((10, 1));
+        queue!(frame, PS("a".bold().dark_grey()), P("b")).unwrap();
+        present(&mut frame);
+        queue!(frame, MoveTo(0, 0), P("\x1b[38;5;8m\x1b[1ma\x1b[0mb")).unwrap();
+        let unchanged = present(&mut frame);
+        assert!(
+            !unchanged.contains('a') && !unchanged.contains('b'),
+            "{unchanged:?}"
+        );
+    }
+
+    #[test]
+    fn cursor_movements_stop_at_the_right_edge() {
+        let mut frame = Frame::new((5, 2));
+        queue!(frame, MoveTo(9, 0), P("a"), MoveTo(0, 1), P("\x1b[9Gb")).unwrap();
+        queue!(frame, MoveTo(0, 1), P("\x1b[9Cc")).unwrap();
+        let symbols = frame.cells.iter().map(|cell| cell.symbol);
+        assert_eq!(symbols.collect::<String>(), "    a    c");
+    }
+
+    #[test]
+    fn clearing_down_removes_wrapped_lines() {
+        let mut frame = Frame::new((20, 4));
+        queue!(frame, MoveTo(0, 2), P("a long status line that wraps")).unwrap();
+        present(&mut frame);
+        queue!(
+            frame,
+            MoveTo(0, 2),
+            Clear(ClearType::FromCursorDown),
+            P("short")
+        )
+        .unwrap();
+        let symbols = frame.cells[40..].iter().map(|cell| cell.symbol);
+        assert_eq!(symbols.collect::<String>().trim_end(), "short");
+    }
+}
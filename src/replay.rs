@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::game::{Game, Move};
+
+/// A finished game as saved to a file: the seed and settings it was dealt with, and every move made,
+/// which is enough to deal the same cards and play the game back
+#[derive(Serialize)]
+pub struct Replay {
+    /// The seed as a string, as it may not fit in a TOML integer
+    pub seed: String,
+    pub table_size: usize,
+    pub extra_draw: usize,
+    pub refill_extras: bool,
+    pub call_no_set: bool,
+    /// Length of a time-attack game, in seconds
+    pub time_limit: Option<u64>,
+    pub moves: Vec<RecordedMove>,
+}
+
+#[derive(Serialize)]
+pub struct RecordedMove {
+    /// Milliseconds since the start of the game
+    pub time: u64,
+    #[serde(flatten)]
+    pub action: Move,
+}
+
+impl Replay {
+    /// Records a game, or returns None if it was not dealt from a seed
+    pub fn new(game: &Game) -> Option<Self> {
+        let config = game.config();
+        Some(Self {
+            seed: game.seed?.to_string(),
+            table_size: config.table_size,
+            extra_draw: config.extra_draw,
+            refill_extras: config.refill_extras,
+            call_no_set: config.call_no_set,
+            time_limit: config.time_limit.map(|limit| limit.as_secs()),
+            moves: game
+                .moves()
+                .iter()
+                .map(|(time, action)| RecordedMove {
+                    time: time.as_millis() as u64,
+                    action: *action,
+                })
+                .collect(),
+        })
+    }
+
+    /// Writes the replay to `rust-set-<seed>.toml` in the given directory, adding a number to the name
+    /// rather than overwriting an earlier replay. Returns the path written to.
+    pub fn save(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        let contents = toml::to_string(self).map_err(std::io::Error::other)?;
+        let path = (1..)
+            .map(|number| match number {
+                1 => dir.join(format!("rust-set-{}.toml", self.seed)),
+                _ => dir.join(format!("rust-set-{}-{number}.toml", self.seed)),
+            })
+            .find(|path| !path.exists())
+            .unwrap();
+        std::fs::write(&path, contents)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameConfig;
+
+    #[test]
+    fn moves_are_saved_as_an_array_of_tables() {
+        let mut game = Game::from_seed(42, GameConfig::default());
+        game.select_card(0).unwrap();
+        game.select_card(1).unwrap();
+        game.deselect_card(1).unwrap();
+        // neither is a move: there is no 22nd card, and the 3rd card is not selected
+        assert!(game.select_card(21).is_err());
+        assert!(game.deselect_card(2).is_none());
+        game.hint();
+        let text = toml::to_string(&Replay::new(&game).unwrap()).unwrap();
+        let replay = text.parse::<toml::Table>().unwrap();
+        assert_eq!(replay["seed"].as_str(), Some("42"), "{text}");
+        assert_eq!(replay["table_size"].as_integer(), Some(12));
+        assert_eq!(replay["extra_draw"].as_integer(), Some(3));
+        assert_eq!(replay["refill_extras"].as_bool(), Some(false));
+        assert_eq!(replay["call_no_set"].as_bool(), Some(false));
+        assert!(!replay.contains_key("time_limit"));
+        let moves = replay["moves"].as_array().unwrap();
+        let actions = moves
+            .iter()
+            .map(|action| {
+                let action = action.as_table().unwrap();
+                assert!(action["time"].is_integer(), "{text}");
+                let card = action.get("card").and_then(toml::Value::as_integer);
+                (action["action"].as_str().unwrap(), card)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actions,
+            [
+                ("toggle", Some(0)),
+                ("toggle", Some(1)),
+                ("deselect", Some(1)),
+                ("hint", None)
+            ]
+        );
+    }
+}
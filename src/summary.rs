@@ -0,0 +1,176 @@
+use std::{io::Write, time::Duration};
+
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Print as P, PrintStyledContent as PS, StyledContent, Stylize},
+    terminal::{Clear, ClearType},
+};
+
+use crate::{
+    game::Game,
+    locale::{fill, text},
+    personal_best::{compare, laps, PersonalBests},
+    util::format_elapsed,
+};
+
+/// Width of a column of split times, e.g. " 12  00:01:23.456s  +12.3s  -1.2s", with room to spare
+const SPLIT_WIDTH: u16 = 38;
+/// Height of the bar of the slowest set in the chart of time per set
+const CHART_HEIGHT: u16 = 5;
+/// Lines below the split times: blank line, options and note
+const FOOTER_LINES: u16 = 3;
+
+/// What to do once a game is over, chosen on the summary screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Choice {
+    /// Play again with a newly shuffled deck
+    NewDeal,
+    /// Play again with the deck shuffled the same way
+    SameDeal,
+    /// Return to the main menu
+    Menu,
+    Quit,
+}
+
+/// What a key does on the summary screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    /// Save a replay of the game, staying on the summary
+    SaveReplay,
+    /// Leave the summary
+    Choose(Choice),
+}
+
+impl Action {
+    pub fn from_key(key: char) -> Option<Self> {
+        match key {
+            'n' => Some(Self::Choose(Choice::NewDeal)),
+            'r' => Some(Self::Choose(Choice::SameDeal)),
+            's' => Some(Self::SaveReplay),
+            'm' => Some(Self::Choose(Choice::Menu)),
+            'Q' => Some(Self::Choose(Choice::Quit)),
+            _ => None,
+        }
+    }
+}
+
+/// Clears the terminal and shows the results of a finished game on a terminal of the given size,
+/// compared with the personal best splits from before the game (empty if there were none),
+/// with a note (such as where a replay was saved) below the options.
+/// The results are followed by a chart of time per set if there is room, then the split times in as many columns as needed.
+pub fn draw(
+    out: &mut impl Write,
+    game: &Game,
+    (width, height): (u16, u16),
+    best: &[Duration],
+    note: &str,
+) -> std::io::Result<()> {
+    let ended = game.ended.unwrap_or(game.started);
+    let heading = match game.config().time_limit {
+        Some(_) => text("summary.time_up"),
+        None => text("summary.game_over"),
+    };
+    let results = [
+        fill(
+            "summary.time",
+            &[("time", &format_elapsed(ended - game.started))],
+        ),
+        fill("summary.sets", &[("sets", &game.score.sets)]),
+        fill("summary.mistakes", &[("mistakes", &game.score.mistakes())]),
+        fill("summary.hints", &[("hints", &game.score.hints)]),
+    ];
+    queue!(out, Clear(ClearType::All), MoveTo(0, 0), PS(heading.bold()))?;
+    let mut row = 2;
+    for result in results {
+        queue!(out, MoveTo(0, row), P(result))?;
+        row += 1;
+    }
+    if let (Some(time), Some(best_time)) = (game.splits.last(), best.last()) {
+        queue!(
+            out,
+            MoveTo(0, row),
+            P(fill(
+                "summary.best",
+                &[("time", &format_elapsed(*best_time))]
+            )),
+            P(" ("),
+            PS(compare(*time, *best_time)),
+            P(")")
+        )?;
+        row += 1;
+    }
+    if PersonalBests::is_beaten_by(best, game) {
+        queue!(
+            out,
+            MoveTo(0, row),
+            PS(text("summary.new_best").bold().green())
+        )?;
+        row += 1;
+    }
+    let (laps, best_laps) = (laps(&game.splits), laps(best));
+    // the chart needs a blank line, a heading and its bars, leaving room for a heading and one line of split times
+    if !laps.is_empty() && row + 2 + CHART_HEIGHT + 3 + FOOTER_LINES <= height {
+        queue!(out, MoveTo(0, row + 1), PS(text("summary.chart").bold()))?;
+        draw_chart(out, row + 2, width, &laps, &best_laps)?;
+        row += 2 + CHART_HEIGHT;
+    }
+    let mut bottom = row;
+    if !laps.is_empty() {
+        queue!(out, MoveTo(0, row + 1), PS(text("summary.splits").bold()))?;
+        let top = row + 2;
+        let rows = height.saturating_sub(top + FOOTER_LINES).max(1);
+        let columns = (width / SPLIT_WIDTH).max(1);
+        let splits = game.splits.iter().zip(&laps).enumerate();
+        for (index, (split, lap)) in splits.take((rows * columns).into()) {
+            let (column, line) = (index as u16 / rows, index as u16 % rows);
+            queue!(
+                out,
+                MoveTo(column * SPLIT_WIDTH, top + line),
+                P(format!(
+                    "{:>3}  {}  {:<8}",
+                    index + 1,
+                    format_elapsed(*split),
+                    format!("+{:.1}s", lap.as_secs_f32())
+                ))
+            )?;
+            if let Some(best) = best.get(index) {
+                queue!(out, PS(compare(*split, *best)))?;
+            }
+            bottom = bottom.max(top + line);
+        }
+    }
+    queue!(
+        out,
+        MoveTo(0, bottom + 2),
+        PS(text("summary.options").dim()),
+        MoveTo(0, bottom + 3),
+        P(note)
+    )
+}
+
+/// Draws a bar of '#' for each set, as tall as the time taken to find it relative to the slowest set.
+/// Bars are green where the set was found faster than in the personal best, and red where it was slower.
+fn draw_chart(
+    out: &mut impl Write,
+    top: u16,
+    width: u16,
+    laps: &[Duration],
+    best_laps: &[Duration],
+) -> std::io::Result<()> {
+    let slowest = laps.iter().max().unwrap().as_secs_f32().max(f32::EPSILON);
+    // leave a space between bars if there is room
+    let spacing = if laps.len() as u16 * 2 <= width { 2 } else { 1 };
+    for (index, lap) in laps.iter().enumerate().take((width / spacing).into()) {
+        let bar = (lap.as_secs_f32() / slowest * f32::from(CHART_HEIGHT)).ceil() as u16;
+        let styled: StyledContent<&str> = match best_laps.get(index) {
+            Some(best) if lap < best => "#".green(),
+            Some(_) => "#".red(),
+            None => "#".stylize(),
+        };
+        for line in CHART_HEIGHT - bar.min(CHART_HEIGHT)..CHART_HEIGHT {
+            queue!(out, MoveTo(index as u16 * spacing, top + line), PS(styled))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::sync::OnceLock;
+
+use crossterm::style::{Attribute, Color};
+
+static ACTIVE: OnceLock<Theme> = OnceLock::new();
+
+/// How card attributes are rendered. Arrays are indexed in the order of the attribute enums in `card`,
+/// e.g. `colours[0]` is used for `Colour::Red`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Theme {
+    /// Glyphs for pills, diamonds and squiggles
+    pub letters: [char; 3],
+    /// Foreground colours for red, green and purple cards, or None to print cards without colour
+    pub colours: Option<[Color; 3]>,
+    /// Letters added to cards to spell out their colour
+    pub colour_marks: Option<[char; 3]>,
+    /// Attributes used to decorate cards with no underline, one underline and two underlines
+    pub decorations: [Attribute; 3],
+    /// Characters added to cards to spell out their decoration, instead of using `decorations`
+    pub decoration_marks: Option<[char; 3]>,
+    /// Whether big cards are drawn using only ASCII characters
+    pub ascii: bool,
+    /// The names cards' colours are described with, matching the colours they are drawn in
+    pub colour_names: ColourNames,
+}
+
+/// Which set of names describes the colours of cards, see `CardNames::colour_names`
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ColourNames {
+    /// Red, green and purple
+    #[default]
+    Standard,
+    /// Blue, orange and pink, as drawn by the colour-blind theme
+    OkabeIto,
+}
+
+/// Names accepted by `Theme::preset`
+pub static PRESETS: [&str; 4] = ["standard", "colour-blind", "glyph", "ascii"];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            letters: ['O', '>', 'S'],
+            colours: Some([Color::Red, Color::Green, Color::Magenta]),
+            colour_marks: None,
+            // some terminals interpret no bold as double underline???
+            decorations: [
+                Attribute::NoUnderline,
+                Attribute::Underlined,
+                Attribute::NoBold,
+            ],
+            decoration_marks: None,
+            ascii: false,
+            colour_names: ColourNames::Standard,
+        }
+    }
+}
+
+impl Theme {
+    /// Looks up a built-in theme by name
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "standard" => Some(Self::default()),
+            // blue, orange and reddish purple from the Okabe-Ito palette
+            "colour-blind" | "color-blind" => Some(Self {
+                colours: Some([
+                    Color::Rgb {
+                        r: 0,
+                        g: 114,
+                        b: 178,
+                    },
+                    Color::Rgb {
+                        r: 230,
+                        g: 159,
+                        b: 0,
+                    },
+                    Color::Rgb {
+                        r: 204,
+                        g: 121,
+                        b: 167,
+                    },
+                ]),
+                colour_names: ColourNames::OkabeIto,
+                ..Self::default()
+            }),
+            "glyph" => Some(Self {
+                colour_marks: Some(['r', 'g', 'p']),
+                ..Self::default()
+            }),
+            "ascii" => Some(Self {
+                colours: None,
+                colour_marks: Some(['r', 'g', 'p']),
+                decoration_marks: Some(['.', '-', '=']),
+                ascii: true,
+                ..Self::default()
+            }),
+            _ => None,
+        }
+    }
+
+    /// The theme used to render cards. Defaults to the standard theme if none was set.
+    pub fn active() -> &'static Self {
+        ACTIVE.get_or_init(Self::default)
+    }
+
+    /// Sets the theme used to render cards. Has no effect once a card has been rendered.
+    pub fn set_active(theme: Self) {
+        let _ = ACTIVE.set(theme);
+    }
+
+    /// Width of a card printed by `Card::stylise`: three shapes, followed by any marks
+    pub fn compact_width(&self) -> u16 {
+        3 + u16::from(self.colour_marks.is_some()) + u16::from(self.decoration_marks.is_some())
+    }
+}
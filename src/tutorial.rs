@@ -1,57 +1,417 @@
-use crate::card::Card;
+use std::io::Write;
 
-pub static KEYBINDS: &str =
-    "  q w e r t y u
-\r  a s d f g h j
-\r  z x c v b n m
-\r  Q to quit anytime";
+use crossterm::{
+    event::KeyCode,
+    queue,
+    style::{Print as P, PrintStyledContent as PS, Stylize},
+};
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
+};
+
+use crate::{
+    bitset::CardSet,
+    card::{Attribute, Card, Colour, Styling, ATTRIBUTES},
+    keymap::{Keymap, COLUMNS},
+    layout::ROWS,
+    locale::{fill, substitute, text, Locale},
+    markdown,
+    names::CardNames,
+    theme::Theme,
+    util::{read_char, say, Input},
+};
+
+/// Walks through the rules one attribute at a time with quizzes, then asks for a set on a small table.
+/// Returns to the menu as soon as Q is pressed.
 pub fn tutorial() -> Result<(), std::io::Error> {
-    crossterm::terminal::disable_raw_mode()?;
-    crossterm::queue!(std::io::stdout(), crossterm::style::Print(format!("
-Set! is a concentration game invented by Marsha Falco in 1974. \x1b[2mrust-set\x1b[0m is a single-player implementation of Set written in Rust.
-
-\x1b[1mRules\x1b[0m
-In the game, \x1b[2mcards\x1b[0m with patterns are shown to the player and the player aims to find \x1b[2msets\x1b[0m within the given cards. Such cards have four \x1b[2mattributes\x1b[0m: shape, number, colour and decoration. Three cards form a set if, for every single attribute of the above four, that attribute is the same in each card, or that attribute is all different for each card.\x1b[0m After three such cards are found by the player, they are removed into the discard pile, and three new cards are drawn repeatedly from the unused pile to replace the three removed cards such that there are again exactly 12 cards shown. If there is no set within the 12 cards, extra cards drawn in threes until is a set; however, they will not be replenished after the player finds a set unless required by the previous criterion. If the unused pile is depleted, the game ends after the player finds all remaining sets on screen.
-
-\x1b[1mImplementation\x1b[0m
-Virtual cards will be laid out on the screen in a n-by-3 table, where n is a number from 1 to 7. (There is guaranteed to be a set within 21 cards). Players must use the following keybinds to select cards:
-{KEYBINDS}
-That is, q selects the 1st item on the 1st row, and d the third item on the second row. Progress will not be saved if Q is used to exit.
-Altertatively, if supported by the terminal, mouse clicks can also be used to select cards.
-
-Shape, number, colour and shading (replaced with decoration) take the following values:
-- Shape: > (diamond), O (pill) or S (squiggly)
-- Number: > (one), >> (two), >>> (three)
-- Colour: \x1b[31mred\x1b[0m, \x1b[32mgreen\x1b[0m, \x1b[35mpurple\x1b[0m
-- Decoration: no underline, \x1b[4m1 underline\x1b[0m or \x1b[21m2 underlines\x1b[0m
-
-\x1b[1mExamples of sets\x1b[0m
-Go figure out why the following cards are sets:\n")))?;
-let cards = rand::seq::IteratorRandom::choose_multiple(0u8..81, &mut rand::rngs::OsRng, 6).into_iter().map(|x| Card::from_tile_number(x).unwrap()).collect::<Vec<_>>();
-
-let sep = crossterm::style::Print(", ");
-let newline = crossterm::style::Print("; ");
-
-crossterm::queue!(
-    std::io::stdout(),
-    cards[0].stylise(false),
-    sep,
-    cards[1].stylise(false),
-    sep,
-    cards[0].third(&cards[1]).stylise(false),
-    newline,
-    cards[2].stylise(false),
-    sep,
-    cards[3].stylise(false),
-    sep,
-    cards[2].third(&cards[3]).stylise(false),
-    newline,
-    cards[4].stylise(false),
-    sep,
-    cards[5].stylise(false),
-    sep,
-    cards[4].third(&cards[5]).stylise(false),
-    crossterm::style::Print("\n\nHave fun!\n")
-)?;
-crossterm::terminal::enable_raw_mode()
+    let mut out = std::io::stdout();
+    let mut rng = rand::thread_rng();
+    say(
+        &mut out,
+        &format!("\n{}", markdown::to_ansi(text("tutorial.intro"))),
+    )?;
+    if !wait(&mut out)? {
+        return Ok(());
+    }
+    for (step, attribute) in ATTRIBUTES.iter().enumerate() {
+        let example = Card::from_tile_number(rng.gen_range(0..81)).unwrap();
+        say(&mut out, "")?;
+        let [first, second, third] = CardNames::active()
+            .colour_names(Theme::active())
+            .map(|[name, _]| name);
+        say(
+            &mut out,
+            &fill(
+                LESSONS[step],
+                &[("first", &first), ("second", &second), ("third", &third)],
+            ),
+        )?;
+        show_cards(
+            &mut out,
+            &[0, 1, 2].map(|value| example.with(*attribute, value)),
+        )?;
+        say(
+            &mut out,
+            &fill("tutorial.rule", &[("attribute", attribute)]),
+        )?;
+        let mut near_misses = [false, true];
+        near_misses.shuffle(&mut rng);
+        for near_miss in near_misses {
+            // three cards differing only in shape cannot nearly form a set, so numbers vary from the start
+            let introduced = &ATTRIBUTES[..=step.max(1)];
+            let cards = quiz(&mut rng, introduced, near_miss.then_some(*attribute));
+            if !ask(&mut out, &cards, introduced)? {
+                return Ok(());
+            }
+        }
+    }
+    say(&mut out, "")?;
+    say(&mut out, text("tutorial.everything"))?;
+    for _ in 0..3 {
+        let near_miss = rng
+            .gen_bool(0.5)
+            .then(|| *ATTRIBUTES.choose(&mut rng).unwrap());
+        let cards = quiz(&mut rng, &ATTRIBUTES, near_miss);
+        if !ask(&mut out, &cards, &ATTRIBUTES)? {
+            return Ok(());
+        }
+    }
+    if !guided_table(&mut out, &mut rng)? {
+        return Ok(());
+    }
+    let locale = Locale::active();
+    let rules = fill_in(
+        &markdown::to_ansi(locale.tutorial),
+        locale,
+        Theme::active(),
+        Keymap::active(),
+        true,
+    );
+    say(&mut out, "")?;
+    say(&mut out, text("tutorial.summary"))?;
+    say(&mut out, &format!("\n{rules}\n"))?;
+    say(&mut out, text("tutorial.have_fun"))
+}
+
+/// Introductions to each attribute, in the order of `ATTRIBUTES`
+static LESSONS: [&str; 4] = [
+    "tutorial.shape",
+    "tutorial.number",
+    "tutorial.colour",
+    "tutorial.decoration",
+];
+
+/// Waits for a key press. Returns false if Q was pressed.
+fn wait(out: &mut impl Write) -> std::io::Result<bool> {
+    queue!(out, PS(text("tutorial.continue").dim()), P("\r\n"))?;
+    out.flush()?;
+    loop {
+        if let Input::Key(key) = read_char(false)? {
+            return Ok(key != KeyCode::Char('Q'));
+        }
+    }
+}
+
+fn show_cards(out: &mut impl Write, cards: &[Card]) -> std::io::Result<()> {
+    for card in cards {
+        queue!(
+            out,
+            P("  "),
+            card.stylise(false),
+            P(format!("  {card}\r\n"))
+        )?;
+    }
+    Ok(())
+}
+
+/// Deals three cards which differ only in the given attributes, at least two of them for a near miss. They form a set,
+/// unless `near_miss` is given, in which case that attribute is changed on one card to break the set.
+fn quiz(rng: &mut impl Rng, attributes: &[Attribute], near_miss: Option<Attribute>) -> [Card; 3] {
+    loop {
+        let tile = rng.gen_range(0..81);
+        let first = Card::from_tile_number(tile).unwrap();
+        let second = attributes
+            .iter()
+            .fold(Card::from_tile_number(tile).unwrap(), |card, attribute| {
+                card.with(*attribute, rng.gen_range(0..3))
+            });
+        let mut third = first.third(&second);
+        if let Some(attribute) = near_miss {
+            third = third.with(
+                attribute,
+                attribute.value(&third) as u8 + rng.gen_range(1..3),
+            );
+        }
+        let mut tiles = [&first, &second, &third].map(Card::to_tile_number);
+        if tiles[0] != tiles[1] && tiles[1] != tiles[2] && tiles[0] != tiles[2] {
+            tiles.shuffle(rng);
+            return tiles.map(|tile| Card::from_tile_number(tile).unwrap());
+        }
+    }
+}
+
+/// Asks whether three cards form a set, then explains the answer. Returns false if Q was pressed.
+fn ask(out: &mut impl Write, cards: &[Card; 3], attributes: &[Attribute]) -> std::io::Result<bool> {
+    say(out, "")?;
+    show_cards(out, cards)?;
+    say(out, text("tutorial.answer"))?;
+    out.flush()?;
+    let answer = loop {
+        match read_char(false)? {
+            Input::Key(KeyCode::Char('y')) => break true,
+            Input::Key(KeyCode::Char('n')) => break false,
+            Input::Key(KeyCode::Char('Q')) => return Ok(false),
+            _ => {}
+        }
+    };
+    let is_set = Card::broken_attributes(cards.each_ref()).is_empty();
+    if answer == is_set {
+        say(out, &text("tutorial.correct").green().to_string())?;
+    } else {
+        say(out, &text("tutorial.incorrect").red().to_string())?;
+    }
+    explain(out, cards.each_ref(), attributes)?;
+    wait(out)
+}
+
+/// Describes each attribute of three cards, and whether it follows the rule
+fn explain(
+    out: &mut impl Write,
+    cards: [&Card; 3],
+    attributes: &[Attribute],
+) -> std::io::Result<()> {
+    let broken = Card::broken_attributes(cards);
+    for attribute in attributes {
+        let [a, b, c] = cards.map(|card| attribute.describe(card));
+        let verdict = if broken.contains(attribute) {
+            text("tutorial.mixed").red()
+        } else if a == b {
+            text("tutorial.same").green()
+        } else {
+            text("tutorial.different").green()
+        };
+        say(out, &format!("  {attribute}: {a}, {b}, {c} - {verdict}"))?;
+    }
+    if broken.is_empty() {
+        say(out, text("tutorial.is_set"))?;
+    }
+    Ok(())
+}
+
+/// Asks for a set on a 3 by 3 table, explaining wrong guesses and giving a hint after two of them.
+/// Returns false if Q was pressed.
+fn guided_table(out: &mut impl Write, rng: &mut impl Rng) -> std::io::Result<bool> {
+    let keymap = Keymap::active();
+    let (tiles, set) = loop {
+        let tiles = (0u8..81).choose_multiple(rng, 9);
+        if let Some(set) = tiles.iter().copied().collect::<CardSet>().sets().next() {
+            break (tiles, set);
+        }
+    };
+    let cards = tiles
+        .iter()
+        .map(|tile| Card::from_tile_number(*tile).unwrap())
+        .collect::<Vec<_>>();
+    let mut selection = Vec::new();
+    let mut mistakes = 0;
+    say(out, "")?;
+    say(out, text("tutorial.table"))?;
+    show_table(out, keymap, &cards, &selection)?;
+    loop {
+        out.flush()?;
+        let Input::Key(KeyCode::Char(key)) = read_char(false)? else {
+            continue;
+        };
+        if key == 'Q' {
+            return Ok(false);
+        }
+        let Some(index) = keymap.index_of(key).filter(|index| *index < cards.len()) else {
+            continue;
+        };
+        let card = &cards[index];
+        if let Some(position) = selection.iter().position(|selected| *selected == index) {
+            selection.remove(position);
+            queue!(out, P(text("status.deselected")))?;
+        } else {
+            selection.push(index);
+            queue!(out, P(text("status.selected")))?;
+        }
+        queue!(
+            out,
+            P(" "),
+            card.stylise(false),
+            P(format!(" ({card})\r\n"))
+        )?;
+        let [a, b, c] = selection[..] else {
+            continue;
+        };
+        let chosen = [&cards[a], &cards[b], &cards[c]];
+        if Card::broken_attributes(chosen).is_empty() {
+            explain(out, chosen, &ATTRIBUTES)?;
+            say(out, &text("tutorial.found").green().to_string())?;
+            return wait(out);
+        }
+        say(out, &text("tutorial.incorrect").red().to_string())?;
+        explain(out, chosen, &ATTRIBUTES)?;
+        selection.clear();
+        mistakes += 1;
+        if mistakes >= 2 {
+            let index = tiles.iter().position(|tile| *tile == set[0]).unwrap();
+            let hint = format!("{} ({})", cards[index], keymap.key_for(index));
+            say(out, &fill("tutorial.hint", &[("card", &hint)]))?;
+        }
+        show_table(out, keymap, &cards, &selection)?;
+    }
+}
+
+/// Prints a 3 by 3 table of cards, each after the key which selects it
+fn show_table(
+    out: &mut impl Write,
+    keymap: &Keymap,
+    cards: &[Card],
+    selection: &[usize],
+) -> std::io::Result<()> {
+    for row in 0..ROWS {
+        for column in 0..cards.len() / ROWS {
+            let index = row + column * ROWS;
+            queue!(
+                out,
+                P(format!("  {} ", keymap.key_for(index))),
+                cards[index].stylise(selection.contains(&index))
+            )?;
+        }
+        queue!(out, P("\r\n"))?;
+    }
+    Ok(())
+}
+
+/// Describes the mark spelling out an attribute value, if the theme uses marks
+fn mark(marks: Option<[char; 3]>, index: usize) -> String {
+    marks.map_or_else(String::new, |marks| format!(" ({})", marks[index]))
+}
+
+/// Fills in the placeholders of a tutorial. If `styled`, attribute names are coloured and decorated as on
+/// cards and keys are listed as in the game; otherwise the result is plain markdown, as in the README.
+fn fill_in(
+    tutorial: &str,
+    locale: &Locale,
+    theme: &Theme,
+    keymap: &Keymap,
+    styled: bool,
+) -> String {
+    let names = locale.names;
+    let [o, d, s] = theme.letters;
+    let shapes = format!(
+        "{o} ({}), {d} ({}), {s} ({})",
+        names.shapes[0][0], names.shapes[1][0], names.shapes[2][0]
+    );
+    let numbers = format!(
+        "{d} ({}), {d}{d} ({}), {d}{d}{d} ({})",
+        names.counts[0], names.counts[1], names.counts[2]
+    );
+    let colours = [Colour::Red, Colour::Green, Colour::Purple]
+        .map(|colour| {
+            let name = names.colour_names(theme)[colour as usize][0].to_owned();
+            if styled {
+                colour.apply(name.stylize(), theme).to_string()
+                    + &mark(theme.colour_marks, colour as usize)
+            } else {
+                name
+            }
+        })
+        .join(", ");
+    let decorations = [Styling::None, Styling::Underline, Styling::DoubleUnderline]
+        .map(|styling| {
+            let name = names.decorations[styling as usize][0].to_owned();
+            if styled {
+                styling.apply(name.stylize(), theme).to_string()
+                    + &mark(theme.decoration_marks, styling as usize)
+            } else {
+                name
+            }
+        })
+        .join(", ");
+    let keybinds = if styled {
+        keymap.keybinds(true).replace('\r', "")
+    } else {
+        markdown_table(keymap)
+    };
+    substitute(
+        tutorial,
+        &[
+            ("keybinds", &keybinds),
+            ("first_key", &keymap.grid[0][0]),
+            ("second_row_third_key", &keymap.grid[1][2]),
+            ("shapes", &shapes),
+            ("numbers", &numbers),
+            ("colours", &colours),
+            ("decorations", &decorations),
+        ],
+    )
+}
+
+/// The key grid as a markdown table
+fn markdown_table(keymap: &Keymap) -> String {
+    let columns = (0..COLUMNS)
+        .map(|column| format!(" {column} |"))
+        .collect::<String>();
+    let rules = " - |".repeat(COLUMNS);
+    let rows = keymap.grid.iter().enumerate().map(|(row, keys)| {
+        let keys = keys
+            .iter()
+            .map(|key| format!(" {key} |"))
+            .collect::<String>();
+        format!("|      {row}      |{keys}")
+    });
+    [
+        format!("| Row\\\\Column |{columns}"),
+        format!("| ----------- |{rules}"),
+    ]
+    .into_iter()
+    .chain(rows)
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::ENGLISH;
+
+    #[test]
+    fn readme_matches_tutorial() {
+        let readme = include_str!("../README.md");
+        let tutorial = fill_in(
+            ENGLISH.tutorial,
+            &ENGLISH,
+            &Theme::default(),
+            &Keymap::default(),
+            false,
+        );
+        assert!(
+            readme.contains(tutorial.trim_end()),
+            "README.md does not contain docs/tutorial.md, expected:\n{tutorial}"
+        );
+    }
+
+    #[test]
+    fn colours_are_named_as_the_theme_draws_them() {
+        let colour_blind = Theme::preset("colour-blind").unwrap();
+        let tutorial = fill_in(
+            ENGLISH.tutorial,
+            &ENGLISH,
+            &colour_blind,
+            &Keymap::default(),
+            false,
+        );
+        assert!(
+            tutorial.contains("- Colour: blue, orange, pink\n"),
+            "{tutorial}"
+        );
+        assert_eq!(
+            ENGLISH.names.colour_names(&Theme::default()),
+            &[["red", "red"], ["green", "green"], ["purple", "purple"]]
+        );
+    }
 }
@@ -1,41 +1,78 @@
 use crossterm::event::*;
-static KEYMAP: &str = "qwertyuasdfghjzxcvbnm";
-pub fn read_char(capture_clicks: bool) -> Result<(KeyCode, bool), std::io::Error> {
+
+/// How long to wait for input before redrawing a screen with a running timer
+pub const TICK: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// An event the game reacts to
+pub enum Input {
+    Key(KeyCode),
+    /// A left click at the given column and row
+    Click(u16, u16),
+    /// A right click at the given column and row
+    RightClick(u16, u16),
+    /// The terminal was resized to the given number of columns and rows
+    Resize(u16, u16),
+}
+
+/// Waits for the next key press, resize, or (if `capture_clicks` is set) mouse click
+pub fn read_char(capture_clicks: bool) -> Result<Input, std::io::Error> {
     loop {
-        match read()? {
-            Event::Key(ev) => {
-                if ev.kind == KeyEventKind::Press {
-                    return Ok((ev.code, true));
-                }
-            }
-            Event::Mouse(ev) => {
-                if ev.kind == MouseEventKind::Down(MouseButton::Left) {
-                    if capture_clicks {
-                        if ev.row == 0 || ev.row > 3 {
-                            continue;
-                        }
-                        let col = (ev.column + 2) / 8;
-                        if col % 8 == 5 {
-                            continue;
-                        } // ignore pressing on pipe
-                        let row = ev.row - 1;
-                        let index = row * 7 + col;
-                        if index > 20 {
-                            continue;
-                        }
-                        return Ok((
-                            KeyCode::Char(KEYMAP.chars().nth(index.into()).unwrap()),
-                            false,
-                        ));
-                    }
-                }
-            }
-            _ => {}
+        if let Some(input) = to_input(read()?, capture_clicks) {
+            return Ok(input);
         }
     }
 }
 
-pub fn exit(final_remarks: impl FnOnce(), code: i32) -> ! {
+/// Like `read_char`, but gives up and returns None once `timeout` has passed
+pub fn poll_input(
+    capture_clicks: bool,
+    timeout: std::time::Duration,
+) -> Result<Option<Input>, std::io::Error> {
+    let deadline = std::time::Instant::now() + timeout;
+    while poll(deadline.saturating_duration_since(std::time::Instant::now()))? {
+        if let Some(input) = to_input(read()?, capture_clicks) {
+            return Ok(Some(input));
+        }
+    }
+    Ok(None)
+}
+
+fn to_input(event: Event, capture_clicks: bool) -> Option<Input> {
+    match event {
+        Event::Key(ev) if ev.kind == KeyEventKind::Press => Some(Input::Key(ev.code)),
+        Event::Resize(columns, rows) => Some(Input::Resize(columns, rows)),
+        Event::Mouse(ev) if capture_clicks => match ev.kind {
+            MouseEventKind::Down(MouseButton::Left) => Some(Input::Click(ev.column, ev.row)),
+            MouseEventKind::Down(MouseButton::Right) => Some(Input::RightClick(ev.column, ev.row)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Formats a duration as shown by the timer, e.g. "00:01:23.456s"
+pub fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let millis = elapsed.subsec_millis();
+    let seconds = elapsed.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}.{millis:03}s",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Prints text in raw mode, followed by a new line
+pub fn say(out: &mut impl std::io::Write, text: &str) -> std::io::Result<()> {
+    crossterm::queue!(
+        out,
+        crossterm::style::Print(text.replace('\n', "\r\n")),
+        crossterm::style::Print("\r\n")
+    )
+}
+
+/// Leaves raw mode and the alternate screen, putting the cursor back where it was before the game started
+pub fn restore_terminal() {
     use crossterm::{cursor, execute, terminal::*};
     disable_raw_mode().unwrap();
     let _ = execute!(
@@ -46,33 +83,10 @@ pub fn exit(final_remarks: impl FnOnce(), code: i32) -> ! {
         cursor::RestorePosition,
         DisableMouseCapture
     );
-    final_remarks();
-    std::process::exit(code);
 }
 
-pub fn corresponding_index(c: char) -> Option<usize> {
-    match c {
-        'q' => Some(0),
-        'w' => Some(3),
-        'e' => Some(6),
-        'r' => Some(9),
-        't' => Some(12),
-        'y' => Some(15),
-        'u' => Some(18),
-        'a' => Some(1),
-        's' => Some(4),
-        'd' => Some(7),
-        'f' => Some(10),
-        'g' => Some(13),
-        'h' => Some(16),
-        'j' => Some(19),
-        'z' => Some(2),
-        'x' => Some(5),
-        'c' => Some(8),
-        'v' => Some(11),
-        'b' => Some(14),
-        'n' => Some(17),
-        'm' => Some(20),
-        _ => None,
-    }
+pub fn exit(final_remarks: impl FnOnce(), code: i32) -> ! {
+    restore_terminal();
+    final_remarks();
+    std::process::exit(code);
 }